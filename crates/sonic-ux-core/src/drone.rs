@@ -0,0 +1,284 @@
+//! Continuous generative drone/pad layer for the Ambient preset.
+//!
+//! Rather than only firing a `PadChord` on section changes, this maintains
+//! a small bed of held chord voices so idle time turns into an evolving
+//! meditative background instead of silence. Each voice sits on a chord
+//! tone with a slight, near-unison detune - the way layered detuned saws
+//! shimmer - and voices crossfade when the harmony modulates: the old
+//! chord's voices release over [`CROSSFADE_MS`] while the new chord's
+//! voices swell in over the same span. A slow per-voice LFO drifts level
+//! and pan so the bed breathes rather than sitting static.
+
+use std::f32::consts::TAU;
+
+use crate::harmony::HarmonyManager;
+use crate::types::{Mode, MusicEvent};
+
+/// Near-unison detune multipliers voices are assigned from, so the bed
+/// shimmers rather than beats audibly out of tune.
+const DETUNE_SET: [f32; 3] = [0.99, 1.0, 1.01];
+
+/// Octave the drone bed sits in.
+const DRONE_OCTAVE: u8 = 3;
+
+/// How long a modulation crossfade takes: the old chord's voices release
+/// while the new chord's voices swell in over this span.
+const CROSSFADE_MS: f32 = 3000.0;
+
+/// Period of the slow level/pan drift LFO applied to each voice.
+const DRIFT_PERIOD_MS: f32 = 9000.0;
+
+/// How far the drift LFO pulls level (fraction of the base level).
+const DRIFT_LEVEL_DEPTH: f32 = 0.15;
+
+/// How far the drift LFO pulls pan (-1..1 units).
+const DRIFT_PAN_DEPTH: f32 = 0.25;
+
+/// One sustained voice of the drone bed.
+#[derive(Debug, Clone, Copy)]
+struct Voice {
+    note: u8,
+    detune: f32,
+    /// Per-voice phase offset so voices drift out of sync with each other.
+    drift_offset_ms: f32,
+}
+
+/// Maintains a crossfading bed of held chord voices under the Ambient preset.
+#[derive(Debug)]
+pub struct DroneGenerator {
+    rng: fastrand::Rng,
+    /// Number of simultaneous voices in the bed.
+    voice_count: usize,
+    /// Currently-sounding voices, empty until the bed is first started.
+    voices: Vec<Voice>,
+    /// Voices from the previous chord, fading out across a crossfade.
+    releasing: Vec<Voice>,
+    /// Elapsed time (ms) into the current crossfade; at or above
+    /// [`CROSSFADE_MS`] once `voices` is fully swelled in.
+    crossfade_elapsed_ms: f32,
+    /// Running phase (ms) for the per-voice drift LFO.
+    drift_phase_ms: f32,
+    started: bool,
+}
+
+impl DroneGenerator {
+    /// Create a drone generator with the given number of simultaneous voices.
+    pub fn new(seed: u64, voice_count: usize) -> Self {
+        Self {
+            rng: fastrand::Rng::with_seed(seed),
+            voice_count: voice_count.max(1),
+            voices: Vec::new(),
+            releasing: Vec::new(),
+            crossfade_elapsed_ms: CROSSFADE_MS,
+            drift_phase_ms: 0.0,
+            started: false,
+        }
+    }
+
+    /// Stop the bed, releasing any currently-sounding or still-fading voices.
+    pub fn stop(&mut self) -> Vec<MusicEvent> {
+        let mut offs: Vec<MusicEvent> = self
+            .voices
+            .drain(..)
+            .map(|v| MusicEvent::PadVoiceOff { note: v.note })
+            .collect();
+        offs.extend(
+            self.releasing
+                .drain(..)
+                .map(|v| MusicEvent::PadVoiceOff { note: v.note }),
+        );
+        self.started = false;
+        offs
+    }
+
+    /// Advance the bed by `dt_ms`.
+    ///
+    /// `modulation` should carry `Some((root, mode))` on the frame the
+    /// harmony modulates, so the bed can crossfade to the new chord tones:
+    /// the outgoing voices keep sounding, fading out over [`CROSSFADE_MS`]
+    /// while the incoming ones swell in over the same span.
+    pub fn update(
+        &mut self,
+        dt_ms: u64,
+        activity: f32,
+        reduced_motion: bool,
+        modulation: Option<(u8, Mode)>,
+        harmony: &HarmonyManager,
+    ) -> Vec<MusicEvent> {
+        let mut events = Vec::new();
+        self.drift_phase_ms += dt_ms as f32;
+
+        if !self.started {
+            self.swell_in(harmony);
+            self.crossfade_elapsed_ms = CROSSFADE_MS;
+            self.started = true;
+        } else if modulation.is_some() {
+            // Any voices still fading from a prior crossfade are cut short
+            // rather than left to fade forever under a second modulation.
+            for voice in self.releasing.drain(..) {
+                events.push(MusicEvent::PadVoiceOff { note: voice.note });
+            }
+            self.releasing = std::mem::take(&mut self.voices);
+            self.swell_in(harmony);
+            self.crossfade_elapsed_ms = 0.0;
+        } else {
+            self.crossfade_elapsed_ms = (self.crossfade_elapsed_ms + dt_ms as f32).min(CROSSFADE_MS);
+        }
+
+        let fade_in = (self.crossfade_elapsed_ms / CROSSFADE_MS).clamp(0.0, 1.0);
+        let base = self.level(activity, reduced_motion);
+
+        if fade_in >= 1.0 {
+            for voice in self.releasing.drain(..) {
+                events.push(MusicEvent::PadVoiceOff { note: voice.note });
+            }
+        } else {
+            let fade_out_level = base * (1.0 - fade_in);
+            for voice in &self.releasing {
+                events.push(MusicEvent::PadVoiceOn {
+                    note: voice.note,
+                    detune: voice.detune,
+                    level: (fade_out_level + self.drift_level(voice)).max(0.0),
+                    pan: self.drift_pan(voice),
+                });
+            }
+        }
+
+        let swell_level = base * fade_in;
+        for voice in &self.voices {
+            events.push(MusicEvent::PadVoiceOn {
+                note: voice.note,
+                detune: voice.detune,
+                level: (swell_level + self.drift_level(voice)).max(0.0),
+                pan: self.drift_pan(voice),
+            });
+        }
+
+        events
+    }
+
+    /// (Re)build the voice bank from the current chord tones. The caller's
+    /// per-frame `PadVoiceOn` emission (in `update`) carries the swell's
+    /// actual level, so this only needs to assign notes, detune, and a
+    /// drift phase offset.
+    fn swell_in(&mut self, harmony: &HarmonyManager) {
+        self.voices = (0..self.voice_count)
+            .map(|i| {
+                let degree = i * 2; // triad-ish spread across scale degrees
+                let note = harmony.scale_note(degree, DRONE_OCTAVE);
+                let detune = DETUNE_SET[self.rng.usize(..DETUNE_SET.len())];
+                let drift_offset_ms = self.rng.f32() * DRIFT_PERIOD_MS;
+                Voice {
+                    note,
+                    detune,
+                    drift_offset_ms,
+                }
+            })
+            .collect();
+    }
+
+    /// Slow LFO offset applied to a voice's level, so the bed's presence
+    /// breathes gently instead of sitting at a static volume.
+    fn drift_level(&self, voice: &Voice) -> f32 {
+        self.drift_phase(voice).sin() * DRIFT_LEVEL_DEPTH
+    }
+
+    /// Slow LFO offset applied to a voice's pan, so the bed drifts across
+    /// the stereo field instead of sitting locked to center.
+    fn drift_pan(&self, voice: &Voice) -> f32 {
+        // A quarter-period offset so pan and level drift out of phase.
+        (self.drift_phase(voice) + TAU / 4.0).sin() * DRIFT_PAN_DEPTH
+    }
+
+    fn drift_phase(&self, voice: &Voice) -> f32 {
+        let elapsed = self.drift_phase_ms + voice.drift_offset_ms;
+        (elapsed / DRIFT_PERIOD_MS) * TAU
+    }
+
+    /// Map idle activity (and reduced motion) to how present the bed is.
+    ///
+    /// Quiet idle time should swell the bed up; once the user is actively
+    /// interacting, other layers carry the foreground so the bed recedes.
+    fn level(&self, activity: f32, reduced_motion: bool) -> f32 {
+        let base = (0.5 - activity * 0.35).clamp(0.1, 0.5);
+        if reduced_motion {
+            base * 0.6
+        } else {
+            base
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::harmony::Preset;
+
+    #[test]
+    fn first_update_swells_in_a_full_voice_bank() {
+        let mut drone = DroneGenerator::new(1, 3);
+        let harmony = HarmonyManager::new(1, Preset::Ambient);
+
+        let events = drone.update(0, 0.0, false, None, &harmony);
+        let voice_ons = events
+            .iter()
+            .filter(|e| matches!(e, MusicEvent::PadVoiceOn { .. }))
+            .count();
+        assert_eq!(voice_ons, 3);
+    }
+
+    #[test]
+    fn modulation_crossfades_old_voices_out_while_new_ones_swell_in() {
+        let mut drone = DroneGenerator::new(1, 2);
+        let harmony = HarmonyManager::new(1, Preset::Ambient);
+        drone.update(0, 0.0, false, None, &harmony);
+
+        // The instant a modulation lands, the outgoing bed keeps sounding
+        // (fading out) while the incoming bed starts swelling in - nothing
+        // is cut off abruptly.
+        let crossfade_start = drone.update(0, 0.0, false, Some((7, Mode::Lydian)), &harmony);
+        assert_eq!(
+            crossfade_start
+                .iter()
+                .filter(|e| matches!(e, MusicEvent::PadVoiceOn { .. }))
+                .count(),
+            4
+        );
+        assert!(crossfade_start
+            .iter()
+            .all(|e| !matches!(e, MusicEvent::PadVoiceOff { .. })));
+
+        // Once the crossfade span has fully elapsed, the old bed is released.
+        let after_crossfade = drone.update(CROSSFADE_MS as u64, 0.0, false, None, &harmony);
+        assert!(after_crossfade
+            .iter()
+            .any(|e| matches!(e, MusicEvent::PadVoiceOff { .. })));
+    }
+
+    #[test]
+    fn voices_drift_in_pan_over_time() {
+        let mut drone = DroneGenerator::new(1, 1);
+        let harmony = HarmonyManager::new(1, Preset::Ambient);
+        drone.update(0, 0.0, false, None, &harmony);
+
+        let later = drone.update(DRIFT_PERIOD_MS as u64 / 4, 0.0, false, None, &harmony);
+        let voice_on = later
+            .iter()
+            .find(|e| matches!(e, MusicEvent::PadVoiceOn { .. }))
+            .unwrap();
+        if let MusicEvent::PadVoiceOn { pan, .. } = voice_on {
+            assert!(pan.abs() > 0.01, "expected the drift LFO to move pan off-center");
+        }
+    }
+
+    #[test]
+    fn higher_activity_recedes_the_bed() {
+        let mut drone = DroneGenerator::new(1, 2);
+        let harmony = HarmonyManager::new(1, Preset::Ambient);
+        drone.update(0, 0.0, false, None, &harmony);
+
+        let quiet = drone.level(0.0, false);
+        let busy = drone.level(1.0, false);
+        assert!(busy < quiet);
+    }
+}
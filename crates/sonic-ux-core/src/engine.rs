@@ -1,8 +1,13 @@
 //! Main engine that orchestrates all components.
 
+use crate::drone::DroneGenerator;
 use crate::events::EventGenerator;
 use crate::harmony::{ChordDegree, HarmonyManager, Preset};
-use crate::smoothing::{DecayingValue, ParamSmoother};
+use crate::mixer::Mixer;
+use crate::performance::Performance;
+use crate::smoothing::{DecayingValue, EnvelopePool, ParamSmoother, VolumeTable};
+use crate::snapshot::{EngineSnapshot, SNAPSHOT_VERSION};
+use crate::spatial::Spatializer;
 use crate::types::{
     DiagnosticOutput, HarmonyState, HoldState, InteractionEvent, InteractionFrame, Mode,
     MusicEvent, MusicParams, OutputFrame,
@@ -19,6 +24,21 @@ pub struct Engine {
     harmony: HarmonyManager,
     /// Event generator
     events: EventGenerator,
+    /// Phrase interpretation applied to generated events
+    performance: Performance,
+    /// Submix stage applied to events just before they leave the engine
+    mixer: Mixer,
+    /// Generative drone/pad bed maintained under the Ambient preset
+    drone: DroneGenerator,
+    /// Seed the engine was created with (retained for snapshotting)
+    seed: u64,
+    /// Perceptual loudness curve applied to `master`/`density`
+    volume_table: VolumeTable,
+    /// Per-event ADSR envelopes spawned by triggered `Pluck`/`PadChord`
+    /// events, summed into `master`/`density` each frame
+    envelopes: EnvelopePool,
+    /// Derives pan/distance/reverb from pointer position for events and params
+    spatializer: Spatializer,
 
     /// Decaying pointer X (handles sentinel values)
     pointer_x: DecayingValue,
@@ -49,10 +69,20 @@ impl Engine {
         let mut events = EventGenerator::new(seed);
         events.apply_preset(preset);
 
+        let mut performance = Performance::new();
+        performance.set_attributes(preset.phrase_attributes());
+
         Self {
             smoother: ParamSmoother::new(),
             harmony: HarmonyManager::new(seed, preset),
             events,
+            performance,
+            mixer: Mixer::new(),
+            drone: DroneGenerator::new(seed, 3),
+            seed,
+            volume_table: VolumeTable::new(preset.volume_range_db()),
+            envelopes: EnvelopePool::new(),
+            spatializer: Spatializer::new(preset.rolloff_settings()),
             pointer_x: DecayingValue::new(0.5, 0.02),
             pointer_y: DecayingValue::new(0.5, 0.02),
             last_t_ms: 0,
@@ -109,16 +139,31 @@ impl Engine {
         self.update_params(&frame, self.raw_activity);
 
         // Update smoother
-        self.smoother.update();
+        self.smoother.update(dt_ms as f32);
 
         // Generate events
+        let pointer_y = if frame.has_pointer() {
+            frame.pointer_y
+        } else {
+            self.pointer_y.value()
+        };
+        let pointer_x = if frame.has_pointer() {
+            frame.pointer_x
+        } else {
+            self.pointer_x.value()
+        };
         let mut events = self.events.update(
             dt_ms,
             frame.section_id,
             frame.hover_id,
             self.raw_activity,
+            pointer_y,
             &mut self.harmony,
         );
+        events = events
+            .into_iter()
+            .map(|event| self.spatializer.place(event, pointer_x, pointer_y, frame.pointer_speed))
+            .collect();
 
         // Handle mute on tab unfocus
         if !frame.tab_focused && frame.focus {
@@ -128,7 +173,48 @@ impl Engine {
             });
         }
 
-        let hold = self.compute_hold(&frame);
+        // Maintain the ambient drone bed. A Cadence event this frame means
+        // the harmony just modulated, so the bed should crossfade to it.
+        if self.harmony.preset() == Preset::Ambient {
+            let modulation = events.iter().find_map(|e| match e {
+                MusicEvent::Cadence {
+                    to_root, to_mode, ..
+                } => Some((*to_root, *to_mode)),
+                _ => None,
+            });
+            events.extend(self.drone.update(
+                dt_ms,
+                self.raw_activity,
+                self.reduced_motion,
+                modulation,
+                &self.harmony,
+            ));
+        }
+
+        // Shape the raw events into a phrase before they leave the engine.
+        let events = self
+            .performance
+            .shape(events, self.raw_activity, self.reduced_motion);
+
+        let hold = self.mixer.apply_hold(self.compute_hold(&frame));
+        let events = self.mixer.apply(events);
+
+        // Spawn a per-event envelope for every triggered note so held notes
+        // decay and release naturally, then sum the running envelopes into
+        // this frame's master/density instead of a flat step.
+        let envelope_settings = self.harmony.preset().envelope_settings();
+        for event in &events {
+            match event {
+                MusicEvent::Pluck { velocity, .. } => {
+                    self.envelopes.spawn(envelope_settings, *velocity);
+                }
+                MusicEvent::PadChord { velocity, .. } => {
+                    self.envelopes.spawn(envelope_settings, *velocity);
+                }
+                _ => {}
+            }
+        }
+        let envelope_level = self.envelopes.advance(dt_ms as f32);
 
         // Build diagnostics if enabled
         let diagnostics = if self.diagnostics_enabled {
@@ -147,20 +233,25 @@ impl Engine {
             None
         };
 
+        let master_level = (self.smoother.master.value() + envelope_level * 0.5).clamp(0.0, 1.0);
+        let density_level = (self.smoother.density.value() + envelope_level).clamp(0.0, 1.0);
+
         OutputFrame {
             params: MusicParams {
-                master: self.smoother.master.value(),
+                master: self.volume_table.amplitude(master_level),
                 warmth: self.smoother.warmth.value(),
                 brightness: self.smoother.brightness.value(),
                 width: self.smoother.width.value(),
                 motion: self.smoother.motion.value(),
                 reverb: self.smoother.reverb.value(),
-                density: self.smoother.density.value(),
+                density: self.volume_table.amplitude(density_level),
                 tension: self.smoother.tension.value(),
+                pan: self.smoother.pan.value(),
             },
             harmony: self.harmony.state(),
             events,
             hold,
+            envelope_level: envelope_level.clamp(0.0, 1.0),
             diagnostics,
         }
     }
@@ -170,12 +261,23 @@ impl Engine {
         if !self.enabled {
             return Vec::new();
         }
-        if let InteractionEvent::Click { y, weight, .. } = event {
+        let click_pos = if let InteractionEvent::Click { x, y, weight, .. } = event {
             let weight = weight.unwrap_or(1.0).clamp(0.0, 1.0);
             let intensity = (0.6 + weight * 0.4 + y * 0.1).clamp(0.0, 1.0);
             self.click_energy = self.click_energy.max(intensity);
+            Some((x, y))
+        } else {
+            None
+        };
+
+        let events = self.events.process_event(&event, &mut self.harmony);
+        match click_pos {
+            Some((x, y)) => events
+                .into_iter()
+                .map(|event| self.spatializer.place(event, x, y, 0.0))
+                .collect(),
+            None => events,
         }
-        self.events.process_event(&event, &self.harmony)
     }
 
     /// Set the current section (for navigation events).
@@ -197,10 +299,26 @@ impl Engine {
         self.enabled
     }
 
+    /// Enable or disable the Euclidean-rhythm arpeggiator mode.
+    ///
+    /// When enabled, the engine lays down a tempo-synced pulse grid that
+    /// walks the active chord/scale degrees instead of only emitting
+    /// sparse activity-driven accents. `steps` is the length of the pulse
+    /// grid; pulse density follows activity.
+    pub fn set_arpeggiator(&mut self, enabled: bool, tempo_bpm: f32, steps: usize) {
+        self.events.set_arpeggiator(enabled, tempo_bpm, steps);
+    }
+
     /// Set harmony preset.
     pub fn set_preset(&mut self, preset: Preset) {
         self.harmony.set_preset(preset);
         self.events.apply_preset(preset);
+        self.performance.set_attributes(preset.phrase_attributes());
+        self.volume_table = VolumeTable::new(preset.volume_range_db());
+        self.spatializer.set_rolloff(preset.rolloff_settings());
+        if preset != Preset::Ambient {
+            self.drone.stop();
+        }
     }
 
     /// Set scale directly.
@@ -228,6 +346,86 @@ impl Engine {
         self.harmony.preset()
     }
 
+    /// Get a reference to the submix stage, for reading current levels.
+    pub fn mixer(&self) -> &Mixer {
+        &self.mixer
+    }
+
+    /// Get a mutable reference to the submix stage.
+    pub fn mixer_mut(&mut self) -> &mut Mixer {
+        &mut self.mixer
+    }
+
+    /// Replace the submix stage wholesale (e.g. after loading a saved one).
+    pub fn set_mixer(&mut self, mixer: Mixer) {
+        self.mixer = mixer;
+    }
+
+    /// Capture the engine's current musical state so it can be saved and
+    /// resumed later, or registered as a custom preset document.
+    pub fn snapshot(&self) -> EngineSnapshot {
+        EngineSnapshot {
+            version: SNAPSHOT_VERSION,
+            seed: self.seed,
+            preset: self.harmony.preset(),
+            harmony: self.harmony.state(),
+            chord_pool: self.harmony.chord_pool().map(|pool| pool.to_vec()),
+            modulation_rate_override: self.harmony.modulation_rate_override(),
+            time_since_modulation: self.harmony.time_since_modulation(),
+            current_chord: self.harmony.current_chord() as u8,
+            params: MusicParams {
+                master: self.smoother.master.target(),
+                warmth: self.smoother.warmth.target(),
+                brightness: self.smoother.brightness.target(),
+                width: self.smoother.width.target(),
+                motion: self.smoother.motion.target(),
+                reverb: self.smoother.reverb.target(),
+                density: self.smoother.density.target(),
+                tension: self.smoother.tension.target(),
+                pan: self.smoother.pan.target(),
+            },
+            smoothing: self.smoother.coefficients(),
+            mixer: self.mixer.clone(),
+            click_energy: self.click_energy,
+        }
+    }
+
+    /// Restore a previously captured snapshot, reconstructing harmony state,
+    /// preset, smoother targets and coefficients, mixer balance, and click
+    /// energy exactly.
+    pub fn restore(&mut self, snapshot: &EngineSnapshot) {
+        self.seed = snapshot.seed;
+        self.harmony.set_preset(snapshot.preset);
+        self.harmony.restore_state(
+            snapshot.harmony,
+            snapshot.chord_pool.clone(),
+            snapshot.modulation_rate_override,
+            snapshot.time_since_modulation,
+        );
+        self.events.apply_preset(snapshot.preset);
+        self.current_chord = snapshot.current_chord;
+
+        self.smoother.master.set_value(snapshot.params.master);
+        self.smoother.warmth.set_value(snapshot.params.warmth);
+        self.smoother.brightness.set_value(snapshot.params.brightness);
+        self.smoother.width.set_value(snapshot.params.width);
+        self.smoother.motion.set_value(snapshot.params.motion);
+        self.smoother.reverb.set_value(snapshot.params.reverb);
+        self.smoother.density.set_value(snapshot.params.density);
+        self.smoother.tension.set_value(snapshot.params.tension);
+        self.smoother.pan.set_value(snapshot.params.pan);
+        self.smoother.restore_coefficients(snapshot.smoothing);
+
+        self.volume_table = VolumeTable::new(snapshot.preset.volume_range_db());
+        self.performance.set_attributes(snapshot.preset.phrase_attributes());
+        self.spatializer.set_rolloff(snapshot.preset.rolloff_settings());
+        if snapshot.preset != Preset::Ambient {
+            self.drone.stop();
+        }
+        self.mixer = snapshot.mixer.clone();
+        self.click_energy = snapshot.click_energy;
+    }
+
     /// Calculate overall activity level from input frame.
     fn calculate_activity(&self, frame: &InteractionFrame) -> f32 {
         let pointer_activity = frame.pointer_speed;
@@ -274,13 +472,18 @@ impl Engine {
 
     /// Update parameter targets based on input.
     fn update_params(&mut self, frame: &InteractionFrame, activity: f32) {
-        // Map pointer position to stereo width
-        let width = if frame.has_pointer() {
-            (frame.pointer_x - 0.5).abs() * 2.0
+        let pointer_x = if frame.has_pointer() {
+            frame.pointer_x
         } else {
-            (self.pointer_x.value() - 0.5).abs() * 2.0
+            self.pointer_x.value()
         };
 
+        // Map pointer position to stereo width
+        let width = (pointer_x - 0.5).abs() * 2.0;
+
+        // Map pointer position to stereo pan via the spatializer
+        let pan = self.spatializer.pan(pointer_x);
+
         // Map pointer position to brightness
         let pointer_y = if frame.has_pointer() {
             frame.pointer_y
@@ -296,7 +499,8 @@ impl Engine {
         let motion = activity * 0.6; // More activity = more modulation
         let scroll_energy = frame.scroll_v.abs().clamp(0.0, 1.0);
         let reduced_boost = if frame.reduced_motion { 0.2 } else { 0.0 };
-        let reverb = (0.2 + scroll_energy * 0.6 + reduced_boost).clamp(0.0, 1.0);
+        let distance_reverb = self.spatializer.reverb_send(pointer_y, frame.pointer_speed);
+        let reverb = (0.2 + scroll_energy * 0.6 + reduced_boost + distance_reverb).clamp(0.0, 1.0);
         let density = activity; // Direct mapping
         let scroll_spike = ((scroll_energy - 0.4) / 0.6).clamp(0.0, 1.0);
         let tension = (self.harmony.state().tension * 0.35
@@ -312,6 +516,7 @@ impl Engine {
         self.smoother.reverb.set_target(reverb);
         self.smoother.density.set_target(density);
         self.smoother.tension.set_target(tension);
+        self.smoother.pan.set_target(pan);
     }
 }
 
@@ -437,6 +642,48 @@ mod tests {
         assert_eq!(engine.preset(), Preset::Dramatic);
     }
 
+    #[test]
+    fn pointer_position_drives_pan_param() {
+        let mut engine = Engine::new(42, Preset::Ambient);
+        let frame = InteractionFrame {
+            t_ms: 16,
+            pointer_x: 0.9,
+            pointer_y: 0.5,
+            pointer_down: false,
+            focus: true,
+            tab_focused: true,
+            ..Default::default()
+        };
+
+        // Several frames so the smoother converges toward the target.
+        let mut output = engine.update(frame);
+        for _ in 0..50 {
+            output = engine.update(frame);
+        }
+
+        assert!(output.params.pan > 0.5);
+    }
+
+    #[test]
+    fn click_pluck_is_panned_to_its_click_position() {
+        let mut engine = Engine::new(42, Preset::Ambient);
+        let events = engine.event(InteractionEvent::Click {
+            x: 0.1,
+            y: 0.5,
+            target_id: 1,
+            weight: None,
+        });
+
+        let pluck = events
+            .iter()
+            .find(|e| matches!(e, MusicEvent::Pluck { .. }))
+            .expect("click should produce a pluck");
+        match pluck {
+            MusicEvent::Pluck { pan, .. } => assert!(*pan < -0.5),
+            _ => unreachable!(),
+        }
+    }
+
     #[test]
     fn test_scale_change() {
         let mut engine = Engine::new(42, Preset::Ambient);
@@ -446,4 +693,19 @@ mod tests {
         assert_eq!(state.root, 9);
         assert_eq!(state.mode, Mode::Minor);
     }
+
+    #[test]
+    fn snapshot_restore_round_trips_harmony_state() {
+        let mut engine = Engine::new(42, Preset::Ambient);
+        engine.set_scale(7, Mode::Dorian);
+        let snap = engine.snapshot();
+
+        let mut fresh = Engine::new(1, Preset::Playful);
+        fresh.restore(&snap);
+
+        let state = fresh.harmony_state();
+        assert_eq!(state.root, 7);
+        assert_eq!(state.mode, Mode::Dorian);
+        assert_eq!(fresh.preset(), Preset::Ambient);
+    }
 }
@@ -2,7 +2,7 @@
 //!
 //! Converts interaction events and state changes into musical events.
 
-use crate::harmony::{ChordDegree, HarmonyManager, Preset};
+use crate::harmony::{HarmonyManager, Preset};
 use crate::types::{InteractionEvent, MusicEvent};
 
 /// Generates musical events from interactions.
@@ -20,8 +20,28 @@ pub struct EventGenerator {
     last_section_id: u32,
     /// Last hover ID (for detecting changes)
     last_hover_id: u32,
+
+    /// Whether the Euclidean-rhythm arpeggiator is running
+    arp_enabled: bool,
+    /// Arpeggiator tempo in beats per minute
+    arp_tempo_bpm: f32,
+    /// Number of steps in the arpeggiator's pulse grid
+    arp_steps: usize,
+    /// Milliseconds per step, derived from tempo/subdivision
+    arp_step_ms: f32,
+    /// Accumulated time since the last step advance
+    arp_elapsed_ms: f32,
+    /// Current position in the pulse grid
+    arp_step_index: usize,
 }
 
+/// Subdivisions per beat used to derive the arpeggiator's step duration and
+/// to decide which steps land on a "strong" beat.
+const ARP_SUBDIVISION: usize = 4;
+/// Scale degrees (relative to the current chord root) the arpeggiator cycles
+/// through, i.e. the triad tones of whatever chord is currently active.
+const ARP_DEGREE_CYCLE: [usize; 3] = [0, 2, 4];
+
 impl EventGenerator {
     /// Create a new event generator.
     pub fn new(seed: u64) -> Self {
@@ -32,9 +52,28 @@ impl EventGenerator {
             event_density: 0.6,
             last_section_id: 0,
             last_hover_id: 0,
+            arp_enabled: false,
+            arp_tempo_bpm: 96.0,
+            arp_steps: 16,
+            arp_step_ms: step_ms(96.0),
+            arp_elapsed_ms: 0.0,
+            arp_step_index: 0,
         }
     }
 
+    /// Enable or disable the Euclidean-rhythm arpeggiator mode.
+    ///
+    /// `steps` is the length of the pulse grid (`n`); the pulse count `k` is
+    /// derived each step from the current activity level.
+    pub fn set_arpeggiator(&mut self, enabled: bool, tempo_bpm: f32, steps: usize) {
+        self.arp_enabled = enabled;
+        self.arp_tempo_bpm = tempo_bpm.max(1.0);
+        self.arp_steps = steps.max(1);
+        self.arp_step_ms = step_ms(self.arp_tempo_bpm);
+        self.arp_step_index = 0;
+        self.arp_elapsed_ms = 0.0;
+    }
+
     /// Set event density (0..1). Lower = fewer events.
     pub fn set_density(&mut self, density: f32) {
         self.event_density = density.clamp(0.0, 1.0);
@@ -59,7 +98,7 @@ impl EventGenerator {
     pub fn process_event(
         &mut self,
         event: &InteractionEvent,
-        harmony: &HarmonyManager,
+        harmony: &mut HarmonyManager,
     ) -> Vec<MusicEvent> {
         let mut events = Vec::new();
 
@@ -79,23 +118,19 @@ impl EventGenerator {
                     note,
                     velocity,
                     salience,
+                    pan: 0.0,
                 });
             }
 
-            InteractionEvent::Nav { section_id, .. } => {
-                // Section change triggers chord
-                let degree = match section_id % 4 {
-                    0 => ChordDegree::I,
-                    1 => ChordDegree::IV,
-                    2 => ChordDegree::V,
-                    _ => ChordDegree::VI,
-                };
-
-                let notes = harmony.chord_notes(degree, 3);
+            InteractionEvent::Nav { .. } => {
+                // Section change walks the progression rather than landing
+                // on an isolated triad.
+                let (_, notes) = harmony.progress();
                 events.push(MusicEvent::PadChord {
                     notes,
                     velocity: 0.4,
                     salience: 0.8, // Navigation is high salience
+                    pan: 0.0,
                 });
             }
 
@@ -107,6 +142,7 @@ impl EventGenerator {
                         note,
                         velocity: 0.2,
                         salience: 0.3, // Low salience for hover
+                        pan: 0.0,
                     });
                     self.last_hover_id = *hover_id;
                 }
@@ -121,12 +157,16 @@ impl EventGenerator {
     }
 
     /// Update internal state (call each frame).
+    ///
+    /// `pointer_y` only feeds the arpeggiator's octave mapping; it is ignored
+    /// when the arpeggiator is disabled.
     pub fn update(
         &mut self,
         dt_ms: u64,
         section_id: u32,
         hover_id: u32,
         activity: f32,
+        pointer_y: f32,
         harmony: &mut HarmonyManager,
     ) -> Vec<MusicEvent> {
         self.time_since_event += dt_ms;
@@ -134,18 +174,12 @@ impl EventGenerator {
 
         // Check for section change
         if section_id != self.last_section_id {
-            let degree = match section_id % 4 {
-                0 => ChordDegree::I,
-                1 => ChordDegree::IV,
-                2 => ChordDegree::V,
-                _ => ChordDegree::VI,
-            };
-
-            let notes = harmony.chord_notes(degree, 3);
+            let (_, notes) = harmony.progress();
             events.push(MusicEvent::PadChord {
                 notes,
                 velocity: 0.5,
                 salience: 0.9,
+                pan: 0.0,
             });
             self.last_section_id = section_id;
             self.time_since_event = 0;
@@ -165,11 +199,59 @@ impl EventGenerator {
             events.push(MusicEvent::Accent {
                 strength: activity,
                 salience: activity * 0.6,
+                pan: 0.0,
             });
             self.time_since_event = 0;
         }
 
         self.last_hover_id = hover_id;
+
+        if self.arp_enabled {
+            events.extend(self.advance_arpeggiator(dt_ms, activity, pointer_y, harmony));
+        }
+
+        events
+    }
+
+    /// Step the Euclidean pulse grid forward by `dt_ms` and emit a `Pluck`
+    /// for every onset step that elapses.
+    fn advance_arpeggiator(
+        &mut self,
+        dt_ms: u64,
+        activity: f32,
+        pointer_y: f32,
+        harmony: &HarmonyManager,
+    ) -> Vec<MusicEvent> {
+        let mut events = Vec::new();
+        self.arp_elapsed_ms += dt_ms as f32;
+
+        while self.arp_elapsed_ms >= self.arp_step_ms {
+            self.arp_elapsed_ms -= self.arp_step_ms;
+
+            let n = self.arp_steps;
+            let k = (1.0 + (activity.clamp(0.0, 1.0) * (n - 1).max(0) as f32).round()) as usize;
+            let k = k.clamp(1, n);
+
+            if euclidean_onset(self.arp_step_index, k, n) {
+                let octave = 3 + (pointer_y.clamp(0.0, 1.0) * 3.0) as u8;
+                let degree = harmony.current_chord() as usize
+                    + ARP_DEGREE_CYCLE[self.arp_step_index % ARP_DEGREE_CYCLE.len()];
+                let note = harmony.scale_note(degree, octave);
+
+                let strong_beat = self.arp_step_index % ARP_SUBDIVISION == 0;
+                let velocity = if strong_beat { 0.8 } else { 0.5 };
+
+                events.push(MusicEvent::Pluck {
+                    note,
+                    velocity,
+                    salience: velocity,
+                    pan: 0.0,
+                });
+            }
+
+            self.arp_step_index = (self.arp_step_index + 1) % n;
+        }
+
         events
     }
 
@@ -185,6 +267,21 @@ impl EventGenerator {
     }
 }
 
+/// Milliseconds per arpeggiator step at the given tempo, using
+/// [`ARP_SUBDIVISION`] steps per beat.
+fn step_ms(tempo_bpm: f32) -> f32 {
+    60_000.0 / tempo_bpm / ARP_SUBDIVISION as f32
+}
+
+/// Classic Bjorklund/Euclidean onset test: spread `k` pulses as evenly as
+/// possible across `n` steps. Step `i` is an onset when `(i * k) mod n < k`.
+fn euclidean_onset(i: usize, k: usize, n: usize) -> bool {
+    if n == 0 {
+        return false;
+    }
+    (i * k) % n < k
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -192,7 +289,7 @@ mod tests {
     #[test]
     fn test_click_generates_pluck() {
         let mut gen = EventGenerator::new(42);
-        let harmony = HarmonyManager::new(42, Preset::Ambient);
+        let mut harmony = HarmonyManager::new(42, Preset::Ambient);
 
         let event = InteractionEvent::Click {
             x: 0.5,
@@ -201,8 +298,39 @@ mod tests {
             weight: None,
         };
 
-        let events = gen.process_event(&event, &harmony);
+        let events = gen.process_event(&event, &mut harmony);
         assert!(!events.is_empty());
         assert!(matches!(events[0], MusicEvent::Pluck { .. }));
     }
+
+    #[test]
+    fn euclidean_onset_distributes_pulses_evenly() {
+        // Classic tresillo pattern: k=3, n=8 -> 1 0 0 1 0 0 1 0
+        let onsets: Vec<bool> = (0..8).map(|i| euclidean_onset(i, 3, 8)).collect();
+        assert_eq!(
+            onsets,
+            vec![true, false, false, true, false, false, true, false]
+        );
+    }
+
+    #[test]
+    fn arpeggiator_emits_onsets_on_strong_beats() {
+        let mut gen = EventGenerator::new(42);
+        let mut harmony = HarmonyManager::new(42, Preset::Ambient);
+        gen.set_arpeggiator(true, 120.0, 8);
+
+        let mut all_events = Vec::new();
+        for _ in 0..64 {
+            all_events.extend(gen.update(50, 0, 0, 0.8, 0.5, &mut harmony));
+        }
+
+        assert!(all_events
+            .iter()
+            .any(|e| matches!(e, MusicEvent::Pluck { .. })));
+        // Step 0 is both an onset and a strong beat, so it plays at 0.8.
+        assert!(all_events.iter().any(|e| matches!(
+            e,
+            MusicEvent::Pluck { velocity, .. } if (*velocity - 0.8).abs() < f32::EPSILON
+        )));
+    }
 }
@@ -2,10 +2,15 @@
 //!
 //! Handles key, mode, chord progressions, and modulation.
 
+use serde::{Deserialize, Serialize};
+
+use crate::performance::{Articulation, PhraseAttribute};
+use crate::smoothing::EnvelopeSettings;
+use crate::spatial::RolloffSettings;
 use crate::types::{HarmonyState, Mode};
 
 /// Named presets that configure scale, chord pool, and modulation behavior.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum Preset {
     /// Lush, dreamy - Major/Lydian, slow modulation, rich chords
     #[default]
@@ -61,6 +66,111 @@ impl Preset {
         }
     }
 
+    /// Get the default per-event ADSR envelope settings for this preset.
+    pub fn envelope_settings(&self) -> EnvelopeSettings {
+        match self {
+            // Slow swells that linger, matching the ambient bed.
+            Preset::Ambient => EnvelopeSettings {
+                attack_ms: 40.0,
+                decay_ms: 200.0,
+                sustain: 0.6,
+                sustain_hold_ms: 400.0,
+                release_ms: 600.0,
+            },
+            // Short and soft; sparse presentation stays out of the way.
+            Preset::Minimal => EnvelopeSettings {
+                attack_ms: 10.0,
+                decay_ms: 120.0,
+                sustain: 0.4,
+                sustain_hold_ms: 150.0,
+                release_ms: 350.0,
+            },
+            // Sharp attack, long release for cinematic weight.
+            Preset::Dramatic => EnvelopeSettings {
+                attack_ms: 2.0,
+                decay_ms: 60.0,
+                sustain: 0.7,
+                sustain_hold_ms: 200.0,
+                release_ms: 900.0,
+            },
+            // Snappy and short, matching the bouncy feel.
+            Preset::Playful => EnvelopeSettings {
+                attack_ms: 2.0,
+                decay_ms: 40.0,
+                sustain: 0.5,
+                sustain_hold_ms: 80.0,
+                release_ms: 180.0,
+            },
+        }
+    }
+
+    /// Get the default perceptual loudness range (dB below unity gain) for
+    /// this preset's `master`/`density` mapping.
+    pub fn volume_range_db(&self) -> f32 {
+        match self {
+            Preset::Ambient => 50.0,
+            Preset::Minimal => 40.0,
+            Preset::Dramatic => 70.0,
+            Preset::Playful => 55.0,
+        }
+    }
+
+    /// Get the distance rolloff curve used by the spatializer for this
+    /// preset.
+    pub fn rolloff_settings(&self) -> RolloffSettings {
+        match self {
+            // Wide, forgiving field - the ambient bed shouldn't swing hard.
+            Preset::Ambient => RolloffSettings {
+                curve: 1.5,
+                floor_gain: 0.5,
+                max_reverb_send: 0.6,
+            },
+            // Sparse presentation stays close and dry; little payoff to depth.
+            Preset::Minimal => RolloffSettings {
+                curve: 1.2,
+                floor_gain: 0.6,
+                max_reverb_send: 0.3,
+            },
+            // Sharp falloff for cinematic contrast between near and far.
+            Preset::Dramatic => RolloffSettings {
+                curve: 2.5,
+                floor_gain: 0.15,
+                max_reverb_send: 0.7,
+            },
+            // Snappy and present; only a light sense of depth.
+            Preset::Playful => RolloffSettings {
+                curve: 1.8,
+                floor_gain: 0.4,
+                max_reverb_send: 0.35,
+            },
+        }
+    }
+
+    /// Default phrase attributes [`Performance::render`](crate::performance::Performance::render)
+    /// should use for this preset, so each preset's output has a genuinely
+    /// different expressive character.
+    pub fn phrase_attributes(&self) -> Vec<PhraseAttribute> {
+        match self {
+            // Smooth and swelling, matching the ambient bed.
+            Preset::Ambient => vec![
+                PhraseAttribute::Articulation(Articulation::Legato),
+                PhraseAttribute::Dynamics { from: 0.5, to: 0.85 },
+            ],
+            // Gentle and even; sparse presentation shouldn't draw attention.
+            Preset::Minimal => vec![PhraseAttribute::Dynamics { from: 0.6, to: 0.75 }],
+            // Wide dynamic swing and a cinematic pull toward the end of phrase.
+            Preset::Dramatic => vec![
+                PhraseAttribute::Dynamics { from: 0.4, to: 1.3 },
+                PhraseAttribute::Ritardando(0.2),
+            ],
+            // Snappy and punctuated, matching the bouncy feel.
+            Preset::Playful => vec![
+                PhraseAttribute::Articulation(Articulation::Staccato),
+                PhraseAttribute::Accent(0.3),
+            ],
+        }
+    }
+
     /// Parse preset from string name.
     pub fn from_name(name: &str) -> Option<Self> {
         match name.to_lowercase().as_str() {
@@ -86,12 +196,18 @@ pub struct HarmonyManager {
     chord_pool: Option<Vec<ChordDegree>>,
     /// Time since last modulation (ms)
     time_since_modulation: u64,
+    /// Degree most recently returned by `progress`, i.e. where the next
+    /// progression step walks from.
+    current_chord: ChordDegree,
+    /// Realized notes of the most recently returned chord, used as the
+    /// voice-leading reference for the next `progress` call.
+    last_chord_notes: Option<Vec<u8>>,
     /// RNG for harmonic decisions
     rng: fastrand::Rng,
 }
 
 /// Chord degrees in Roman numeral notation.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ChordDegree {
     I,
     II,
@@ -102,6 +218,97 @@ pub enum ChordDegree {
     VII,
 }
 
+impl ChordDegree {
+    /// Every chord degree, in Roman numeral order.
+    pub const ALL: [ChordDegree; 7] = [
+        ChordDegree::I,
+        ChordDegree::II,
+        ChordDegree::III,
+        ChordDegree::IV,
+        ChordDegree::V,
+        ChordDegree::VI,
+        ChordDegree::VII,
+    ];
+
+    /// This degree's functional-harmony role, used to bias progressions
+    /// toward tonic -> subdominant -> dominant -> tonic motion.
+    fn function(self) -> HarmonicFunction {
+        match self {
+            ChordDegree::I | ChordDegree::VI => HarmonicFunction::Tonic,
+            ChordDegree::II | ChordDegree::IV => HarmonicFunction::Subdominant,
+            ChordDegree::III | ChordDegree::V | ChordDegree::VII => HarmonicFunction::Dominant,
+        }
+    }
+
+    fn scale_degree_index(self) -> usize {
+        match self {
+            ChordDegree::I => 0,
+            ChordDegree::II => 1,
+            ChordDegree::III => 2,
+            ChordDegree::IV => 3,
+            ChordDegree::V => 4,
+            ChordDegree::VI => 5,
+            ChordDegree::VII => 6,
+        }
+    }
+}
+
+/// Functional-harmony role a chord degree plays in a progression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HarmonicFunction {
+    Tonic,
+    Subdominant,
+    Dominant,
+}
+
+/// Chord-extension tier [`HarmonyManager::chord_notes`] currently applies,
+/// escalating with tension toward the preset's tension ceiling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChordExtensionLevel {
+    /// Bare root/third/fifth.
+    Triad,
+    /// Triad plus the scale's 7th degree.
+    Seventh,
+    /// 7th chord plus the scale's 9th degree.
+    Ninth,
+    /// The third replaced with a suspended 2nd or 4th.
+    Suspended,
+}
+
+/// Number of extension tones (7th, 9th) to stack above the triad, and
+/// whether the third should be replaced with a suspension, for `tension`
+/// relative to the preset's `ceiling`.
+fn extension_tier(tension: f32, ceiling: f32) -> (u8, bool) {
+    let ratio = if ceiling > 0.0 { tension / ceiling } else { 0.0 };
+    let suspended = ratio >= 0.9;
+    let extra_tones = if ratio >= 0.66 {
+        2
+    } else if ratio >= 0.33 {
+        1
+    } else {
+        0
+    };
+    (extra_tones, suspended)
+}
+
+/// Base transition weight from one harmonic function to another, favoring
+/// the classic tonic -> subdominant -> dominant -> tonic circuit over
+/// stalling on or retreating from a function.
+fn function_transition_weight(from: HarmonicFunction, to: HarmonicFunction) -> f32 {
+    use HarmonicFunction::*;
+    match (from, to) {
+        (Tonic, Subdominant) => 3.0,
+        (Tonic, Dominant) => 1.0,
+        (Tonic, Tonic) => 0.5,
+        (Subdominant, Dominant) => 3.0,
+        (Subdominant, Tonic) => 1.0,
+        (Subdominant, Subdominant) => 0.5,
+        (Dominant, Tonic) => 3.0,
+        (Dominant, Subdominant) => 0.5,
+        (Dominant, Dominant) => 0.3,
+    }
+}
+
 impl HarmonyManager {
     /// Create a new harmony manager with the given preset.
     pub fn new(seed: u64, preset: Preset) -> Self {
@@ -115,6 +322,8 @@ impl HarmonyManager {
             modulation_rate_override: None,
             chord_pool: None,
             time_since_modulation: 0,
+            current_chord: ChordDegree::I,
+            last_chord_notes: None,
             rng: fastrand::Rng::with_seed(seed),
         }
     }
@@ -151,6 +360,38 @@ impl HarmonyManager {
         self.modulation_rate_override = Some(rate.clamp(0.0, 1.0));
     }
 
+    /// Get the custom chord pool, if one has been set.
+    pub fn chord_pool(&self) -> Option<&[ChordDegree]> {
+        self.chord_pool.as_deref()
+    }
+
+    /// Get the modulation rate override, if one has been set.
+    pub fn modulation_rate_override(&self) -> Option<f32> {
+        self.modulation_rate_override
+    }
+
+    /// Time elapsed since the last modulation (ms). Governs when the next
+    /// modulation roll is allowed; the closest thing to a "pending
+    /// modulation" this stochastic model tracks.
+    pub fn time_since_modulation(&self) -> u64 {
+        self.time_since_modulation
+    }
+
+    /// Set harmonic state, custom chord pool, modulation override, and
+    /// modulation timer directly. Used to restore a saved snapshot.
+    pub fn restore_state(
+        &mut self,
+        state: HarmonyState,
+        chord_pool: Option<Vec<ChordDegree>>,
+        modulation_rate_override: Option<f32>,
+        time_since_modulation: u64,
+    ) {
+        self.state = state;
+        self.chord_pool = chord_pool;
+        self.modulation_rate_override = modulation_rate_override;
+        self.time_since_modulation = time_since_modulation;
+    }
+
     /// Update harmony state based on elapsed time and activity.
     /// Returns Some((new_root, new_mode)) if a modulation occurred.
     pub fn update(&mut self, dt_ms: u64, activity: f32) -> Option<(u8, Mode)> {
@@ -193,27 +434,171 @@ impl HarmonyManager {
         self.state.root + interval + (octave * 12)
     }
 
-    /// Get chord notes for a given degree.
+    /// Get chord notes for a given degree, escalating to richer extensions
+    /// as the current tension rises toward the preset's tension ceiling. See
+    /// [`HarmonyManager::chord_notes_voiced`] for a variant that takes an
+    /// explicit tension instead of the manager's current one.
     pub fn chord_notes(&self, degree: ChordDegree, octave: u8) -> Vec<u8> {
+        self.chord_notes_voiced(degree, octave, self.state.tension)
+    }
+
+    /// Get chord notes for a given degree at an explicit `tension` (0..1),
+    /// rather than the manager's current tension. Below roughly a third of
+    /// the preset's tension ceiling this is a bare triad; further up the
+    /// 7th and then the 9th scale degree are stacked on top, and at the top
+    /// of the range the third is replaced by a suspended 2nd or 4th.
+    pub fn chord_notes_voiced(&self, degree: ChordDegree, octave: u8, tension: f32) -> Vec<u8> {
         let intervals = self.state.mode.intervals();
-        let degree_idx = match degree {
-            ChordDegree::I => 0,
-            ChordDegree::II => 1,
-            ChordDegree::III => 2,
-            ChordDegree::IV => 3,
-            ChordDegree::V => 4,
-            ChordDegree::VI => 5,
-            ChordDegree::VII => 6,
+        let len = intervals.len();
+        let degree_idx = degree.scale_degree_index();
+        let base = self.state.root + (octave * 12);
+
+        // Scale degrees stack in thirds; `steps` past the chord's own degree
+        // walks up that stack, carrying an octave bump once it wraps.
+        let interval_at = |steps: usize| -> u8 {
+            let idx = degree_idx + steps;
+            intervals[idx % len] + (idx / len) as u8 * 12
         };
 
-        // Build triad from scale degrees
-        let root = intervals[degree_idx % intervals.len()];
-        let third = intervals[(degree_idx + 2) % intervals.len()];
-        let fifth = intervals[(degree_idx + 4) % intervals.len()];
+        let (extra_tones, suspended) = extension_tier(tension, self.preset.tension_ceiling());
 
-        let base = self.state.root + (octave * 12);
-        vec![base + root, base + third, base + fifth]
+        let third_or_suspension = if suspended {
+            // A dominant-function chord resolves more naturally suspended on
+            // its 4th; everything else keeps the brighter sus2 color.
+            if degree.function() == HarmonicFunction::Dominant {
+                interval_at(3)
+            } else {
+                interval_at(1)
+            }
+        } else {
+            interval_at(2)
+        };
+
+        let mut notes = vec![base + interval_at(0), base + third_or_suspension, base + interval_at(4)];
+        if extra_tones >= 1 {
+            notes.push(base + interval_at(6));
+        }
+        if extra_tones >= 2 {
+            notes.push(base + interval_at(8));
+        }
+        notes
+    }
+
+    /// The chord-extension tier `chord_notes` would currently apply, for
+    /// hosts that want to report escalating harmonic richness (e.g. a synth
+    /// that lights up extra voices as activity rises).
+    pub fn extension_level(&self) -> ChordExtensionLevel {
+        let (extra_tones, suspended) = extension_tier(self.state.tension, self.preset.tension_ceiling());
+        match (extra_tones, suspended) {
+            (_, true) => ChordExtensionLevel::Suspended,
+            (2, _) => ChordExtensionLevel::Ninth,
+            (1, _) => ChordExtensionLevel::Seventh,
+            _ => ChordExtensionLevel::Triad,
+        }
     }
+
+    /// The degree most recently returned by `progress`.
+    pub fn current_chord(&self) -> ChordDegree {
+        self.current_chord
+    }
+
+    /// Walk the chord pool (or every degree, if no pool is set) one step
+    /// using a functional-harmony transition model, then realize the chosen
+    /// degree's notes with voice leading against the previous chord.
+    ///
+    /// Returns the new degree and its realized notes so callers can emit a
+    /// smooth progression instead of isolated triads.
+    pub fn progress(&mut self) -> (ChordDegree, Vec<u8>) {
+        let next = self.pick_next_chord();
+        let raw_notes = self.chord_notes(next, 3);
+        let notes = match &self.last_chord_notes {
+            Some(previous) => voice_lead(&raw_notes, previous),
+            None => raw_notes,
+        };
+
+        self.current_chord = next;
+        self.last_chord_notes = Some(notes.clone());
+        (next, notes)
+    }
+
+    /// Pick the next chord degree from the active chord pool via a weighted
+    /// random walk biased toward tonic -> subdominant -> dominant -> tonic
+    /// motion, with dominants weighted further at high tension.
+    fn pick_next_chord(&mut self) -> ChordDegree {
+        let candidates: Vec<ChordDegree> = self
+            .chord_pool
+            .clone()
+            .unwrap_or_else(|| ChordDegree::ALL.to_vec());
+        if candidates.is_empty() {
+            return self.current_chord;
+        }
+
+        let weights: Vec<f32> = candidates
+            .iter()
+            .map(|&degree| self.transition_weight(degree))
+            .collect();
+        let total: f32 = weights.iter().sum();
+        if total <= 0.0 {
+            return candidates[0];
+        }
+
+        let mut roll = self.rng.f32() * total;
+        for (&degree, &weight) in candidates.iter().zip(weights.iter()) {
+            if roll < weight {
+                return degree;
+            }
+            roll -= weight;
+        }
+        candidates[candidates.len() - 1]
+    }
+
+    /// Weight of transitioning from the current chord to `to`, biased by
+    /// harmonic function and, for dominants, by the current tension.
+    fn transition_weight(&self, to: ChordDegree) -> f32 {
+        let base = function_transition_weight(self.current_chord.function(), to.function());
+        if to.function() == HarmonicFunction::Dominant {
+            base * (1.0 + self.state.tension * 2.0)
+        } else {
+            base
+        }
+    }
+}
+
+/// Choose octave placements for `new_notes` that minimize each voice's
+/// distance from the corresponding note in `previous`, so progressions move
+/// by the smallest total semitone distance rather than jumping to a bare
+/// root-position triad every time.
+fn voice_lead(new_notes: &[u8], previous: &[u8]) -> Vec<u8> {
+    new_notes
+        .iter()
+        .enumerate()
+        .map(|(i, &note)| match previous.get(i) {
+            Some(&prev) => closest_octave(note, prev),
+            None => note,
+        })
+        .collect()
+}
+
+/// Shift `note` by whole octaves (up to two in either direction) to land as
+/// close as possible to `target`, without leaving the MIDI note range.
+fn closest_octave(note: u8, target: u8) -> u8 {
+    let note = note as i16;
+    let target = target as i16;
+    let mut best = note;
+    let mut best_distance = (note - target).abs();
+
+    for shift in [-24, -12, 12, 24] {
+        let candidate = note + shift;
+        if (0..=127).contains(&candidate) {
+            let distance = (candidate - target).abs();
+            if distance < best_distance {
+                best = candidate;
+                best_distance = distance;
+            }
+        }
+    }
+
+    best as u8
 }
 
 /// Linear interpolation helper.
@@ -236,4 +621,107 @@ mod tests {
         assert_eq!(Mode::Major.intervals(), &[0, 2, 4, 5, 7, 9, 11]);
         assert_eq!(Mode::PentatonicMinor.intervals(), &[0, 3, 5, 7, 10]);
     }
+
+    #[test]
+    fn progress_walks_the_custom_chord_pool_only() {
+        let mut harmony = HarmonyManager::new(7, Preset::Ambient);
+        harmony.set_chord_pool(vec![ChordDegree::I, ChordDegree::V]);
+
+        for _ in 0..20 {
+            let (degree, _) = harmony.progress();
+            assert!(matches!(degree, ChordDegree::I | ChordDegree::V));
+        }
+    }
+
+    #[test]
+    fn high_tension_favors_dominant_transitions() {
+        let mut calm = HarmonyManager::new(1, Preset::Ambient);
+        calm.restore_state(
+            HarmonyState {
+                root: 0,
+                mode: Mode::Major,
+                tension: 0.0,
+            },
+            None,
+            None,
+            0,
+        );
+        let mut tense = HarmonyManager::new(1, Preset::Ambient);
+        tense.restore_state(
+            HarmonyState {
+                root: 0,
+                mode: Mode::Major,
+                tension: 1.0,
+            },
+            None,
+            None,
+            0,
+        );
+
+        let dominant_count = |harmony: &mut HarmonyManager| {
+            (0..200)
+                .filter(|_| harmony.progress().0.function() == HarmonicFunction::Dominant)
+                .count()
+        };
+
+        assert!(dominant_count(&mut tense) > dominant_count(&mut calm));
+    }
+
+    #[test]
+    fn voice_leading_pulls_distant_octaves_toward_the_previous_chord() {
+        let previous = vec![60, 64, 67]; // C major triad around middle C
+        let new_notes = vec![74, 77, 81]; // D minor triad, an octave plus a step higher
+        let led = voice_lead(&new_notes, &previous);
+
+        for ((led_note, &prev_note), &raw_note) in
+            led.iter().zip(previous.iter()).zip(new_notes.iter())
+        {
+            let direct_distance = (raw_note as i16 - prev_note as i16).abs();
+            let led_distance = (*led_note as i16 - prev_note as i16).abs();
+            assert!(led_distance < direct_distance);
+            assert!(led_distance <= 6);
+        }
+    }
+
+    #[test]
+    fn chord_notes_voiced_is_a_bare_triad_at_low_tension() {
+        let manager = HarmonyManager::new(42, Preset::Ambient);
+        let notes = manager.chord_notes_voiced(ChordDegree::I, 4, 0.0);
+        assert_eq!(notes.len(), 3);
+    }
+
+    #[test]
+    fn chord_notes_voiced_stacks_a_7th_then_a_9th_as_tension_climbs() {
+        let manager = HarmonyManager::new(42, Preset::Dramatic);
+        let ceiling = manager.preset.tension_ceiling();
+
+        let seventh = manager.chord_notes_voiced(ChordDegree::I, 4, ceiling * 0.5);
+        assert_eq!(seventh.len(), 4);
+
+        let ninth = manager.chord_notes_voiced(ChordDegree::I, 4, ceiling * 0.8);
+        assert_eq!(ninth.len(), 5);
+    }
+
+    #[test]
+    fn chord_notes_voiced_suspends_the_third_at_peak_tension() {
+        let manager = HarmonyManager::new(42, Preset::Dramatic);
+        let ceiling = manager.preset.tension_ceiling();
+
+        let triad = manager.chord_notes_voiced(ChordDegree::I, 4, 0.0);
+        let suspended = manager.chord_notes_voiced(ChordDegree::I, 4, ceiling * 0.95);
+
+        assert_ne!(triad[1], suspended[1]);
+    }
+
+    #[test]
+    fn extension_level_tracks_the_current_tension_against_the_ceiling() {
+        let mut manager = HarmonyManager::new(42, Preset::Dramatic);
+        // The initial tension (0.3) is already a third of Dramatic's 0.9
+        // ceiling, so start from silence to observe the bare triad.
+        manager.state.tension = 0.0;
+        assert_eq!(manager.extension_level(), ChordExtensionLevel::Triad);
+
+        manager.state.tension = manager.preset.tension_ceiling() * 0.95;
+        assert_eq!(manager.extension_level(), ChordExtensionLevel::Suspended);
+    }
 }
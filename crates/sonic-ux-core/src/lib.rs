@@ -46,10 +46,25 @@
 
 mod types;
 mod engine;
+mod drone;
 pub mod harmony;
 mod smoothing;
 mod events;
+pub mod midi;
+pub mod mixer;
+pub mod performance;
+pub mod snapshot;
+pub mod spatial;
+#[cfg(feature = "synth")]
+pub mod synth;
 
 pub use types::*;
 pub use engine::Engine;
 pub use harmony::Preset;
+pub use midi::{MidiMessage, MidiRenderer};
+pub use mixer::{ChannelStrip, Mixer};
+pub use performance::{Articulation, PerfEvent, PerformedEvent, PhraseAttribute, Performance};
+pub use snapshot::{CustomPreset, EngineSnapshot, PresetLibrary};
+pub use spatial::{RolloffSettings, Spatializer};
+#[cfg(feature = "synth")]
+pub use synth::{AdsrSettings, SynthRenderer, VoiceManager, Waveform};
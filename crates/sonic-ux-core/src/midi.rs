@@ -0,0 +1,464 @@
+//! MIDI output backend.
+//!
+//! Translates an [`OutputFrame`] into standard 3-byte MIDI messages so the
+//! engine can drive any external synth or DAW instead of only emitting
+//! abstract parameters. This module performs no I/O - callers forward the
+//! returned messages to `midir`, a file writer, or anything else that
+//! understands raw MIDI bytes.
+
+use crate::types::{HoldState, Mode, MusicEvent, MusicParams, OutputFrame};
+
+/// A raw 3-byte MIDI message (status, data1, data2).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MidiMessage {
+    pub status: u8,
+    pub data1: u8,
+    pub data2: u8,
+}
+
+impl MidiMessage {
+    fn note_on(channel: u8, note: u8, velocity: u8) -> Self {
+        Self {
+            status: 0x90 | (channel & 0x0F),
+            data1: note & 0x7F,
+            data2: velocity & 0x7F,
+        }
+    }
+
+    fn note_off(channel: u8, note: u8) -> Self {
+        Self {
+            status: 0x80 | (channel & 0x0F),
+            data1: note & 0x7F,
+            data2: 0,
+        }
+    }
+
+    fn control_change(channel: u8, controller: u8, value: u8) -> Self {
+        Self {
+            status: 0xB0 | (channel & 0x0F),
+            data1: controller & 0x7F,
+            data2: value & 0x7F,
+        }
+    }
+
+    /// Program Change is a 2-byte message; `data2` is unused and left 0.
+    fn program_change(channel: u8, program: u8) -> Self {
+        Self {
+            status: 0xC0 | (channel & 0x0F),
+            data1: program & 0x7F,
+            data2: 0,
+        }
+    }
+
+    /// Pack this message into its raw 3-byte wire form.
+    pub fn as_bytes(&self) -> [u8; 3] {
+        [self.status, self.data1, self.data2]
+    }
+}
+
+/// CC assignments for the continuous `MusicParams` fields.
+pub mod cc {
+    pub const BRIGHTNESS: u8 = 74;
+    pub const WARMTH: u8 = 71;
+    pub const REVERB: u8 = 91;
+    pub const WIDTH: u8 = 10;
+    pub const MASTER: u8 = 7;
+    pub const TENSION: u8 = 75;
+    pub const DENSITY: u8 = 76;
+}
+
+/// Minimum change in a 0..1 parameter before a new CC message is emitted.
+/// Keeps a steady parameter from flooding the output with redundant CCs.
+const DEFAULT_CC_THRESHOLD: f32 = 1.0 / 127.0;
+
+/// Converts successive `OutputFrame`s into MIDI messages.
+///
+/// Tracks currently-sounding notes - pad/hold voices plus pending `Pluck`
+/// releases - so it can issue correct Note Offs when a `HoldState` ends, a
+/// `Pluck`'s short gate elapses, or a `Mute` event fires, and diffs
+/// `MusicParams` against the previous frame so CC updates are only sent when
+/// a value moves beyond [`DEFAULT_CC_THRESHOLD`].
+#[derive(Debug)]
+pub struct MidiRenderer {
+    /// MIDI channel (0-15) messages are emitted on.
+    channel: u8,
+    /// Minimum param delta before a CC update is emitted.
+    cc_threshold: f32,
+    /// Params from the previous render call, for diffing.
+    last_params: Option<MusicParams>,
+    /// Notes currently sounding from sustained `PadChord` and `PadVoiceOn`
+    /// events.
+    pad_notes: Vec<u8>,
+    /// Note currently sounding from the click-and-hold voice, if any.
+    hold_note: Option<u8>,
+    /// Notes from `Pluck` events awaiting their Note Off. Plucks are
+    /// one-shot rather than held, so their release is deferred to the start
+    /// of the next render call instead of tracked indefinitely.
+    pending_pluck_offs: Vec<u8>,
+    /// Emit a Program Change for the target mode of every `Cadence`.
+    program_change_enabled: bool,
+}
+
+impl MidiRenderer {
+    /// Create a renderer emitting on the given MIDI channel (0-15).
+    pub fn new(channel: u8) -> Self {
+        Self {
+            channel: channel & 0x0F,
+            cc_threshold: DEFAULT_CC_THRESHOLD,
+            last_params: None,
+            pad_notes: Vec::new(),
+            hold_note: None,
+            pending_pluck_offs: Vec::new(),
+            program_change_enabled: false,
+        }
+    }
+
+    /// Set the minimum change in a 0..1 param before a CC is re-sent.
+    pub fn set_cc_threshold(&mut self, threshold: f32) {
+        self.cc_threshold = threshold.max(0.0);
+    }
+
+    /// Change the MIDI channel (0-15) messages are emitted on.
+    pub fn set_channel(&mut self, channel: u8) {
+        self.channel = channel & 0x0F;
+    }
+
+    /// Enable or disable emitting a Program Change on `Cadence` events.
+    /// Off by default, since many synths ignore or mis-map program numbers.
+    pub fn set_program_change_enabled(&mut self, enabled: bool) {
+        self.program_change_enabled = enabled;
+    }
+
+    /// Render one frame's worth of MIDI messages.
+    pub fn render(&mut self, frame: &OutputFrame) -> Vec<MidiMessage> {
+        let mut messages = Vec::new();
+
+        self.flush_pluck_offs(&mut messages);
+
+        for event in &frame.events {
+            self.render_event(event, &mut messages);
+        }
+
+        self.render_hold(frame.hold, &mut messages);
+        self.render_params(&frame.params, &mut messages);
+
+        messages
+    }
+
+    /// Render MIDI messages for a standalone batch of events, e.g. the
+    /// `Vec<MusicEvent>` returned by `Engine::event`, without touching the
+    /// hold voice or param CC diffing that only make sense per `update()`.
+    pub fn render_events(&mut self, events: &[MusicEvent]) -> Vec<MidiMessage> {
+        let mut messages = Vec::new();
+        self.flush_pluck_offs(&mut messages);
+        for event in events {
+            self.render_event(event, &mut messages);
+        }
+        messages
+    }
+
+    fn render_event(&mut self, event: &MusicEvent, out: &mut Vec<MidiMessage>) {
+        match event {
+            MusicEvent::Pluck { note, velocity, .. } => {
+                out.push(MidiMessage::note_on(
+                    self.channel,
+                    *note,
+                    scale_velocity(*velocity),
+                ));
+                self.pending_pluck_offs.push(*note);
+            }
+            MusicEvent::PadChord { notes, velocity, .. } => {
+                for &old in &self.pad_notes {
+                    if !notes.contains(&old) {
+                        out.push(MidiMessage::note_off(self.channel, old));
+                    }
+                }
+                for &new in notes {
+                    if !self.pad_notes.contains(&new) {
+                        out.push(MidiMessage::note_on(
+                            self.channel,
+                            new,
+                            scale_velocity(*velocity),
+                        ));
+                    }
+                }
+                self.pad_notes = notes.clone();
+            }
+            MusicEvent::Mute { on, .. } => {
+                if *on {
+                    self.release_all(out);
+                }
+            }
+            MusicEvent::PadVoiceOn { note, level, .. } => {
+                if !self.pad_notes.contains(note) {
+                    out.push(MidiMessage::note_on(
+                        self.channel,
+                        *note,
+                        scale_velocity(*level),
+                    ));
+                    self.pad_notes.push(*note);
+                }
+            }
+            MusicEvent::PadVoiceOff { note } => {
+                if let Some(pos) = self.pad_notes.iter().position(|n| n == note) {
+                    self.pad_notes.remove(pos);
+                    out.push(MidiMessage::note_off(self.channel, *note));
+                }
+            }
+            MusicEvent::Cadence { to_mode, .. } => {
+                if self.program_change_enabled {
+                    out.push(MidiMessage::program_change(
+                        self.channel,
+                        mode_to_program(*to_mode),
+                    ));
+                }
+            }
+            MusicEvent::Accent { .. } => {}
+        }
+    }
+
+    fn render_hold(&mut self, hold: Option<HoldState>, out: &mut Vec<MidiMessage>) {
+        match (self.hold_note, hold) {
+            (Some(old), Some(new)) if old != new.note => {
+                out.push(MidiMessage::note_off(self.channel, old));
+                out.push(MidiMessage::note_on(
+                    self.channel,
+                    new.note,
+                    scale_velocity(new.velocity),
+                ));
+                self.hold_note = Some(new.note);
+            }
+            (None, Some(new)) => {
+                out.push(MidiMessage::note_on(
+                    self.channel,
+                    new.note,
+                    scale_velocity(new.velocity),
+                ));
+                self.hold_note = Some(new.note);
+            }
+            (Some(old), None) => {
+                out.push(MidiMessage::note_off(self.channel, old));
+                self.hold_note = None;
+            }
+            _ => {}
+        }
+    }
+
+    /// Issue Note Offs for every currently-sounding note (pad bed, hold
+    /// voice, and any pluck awaiting its gate) and forget them.
+    fn release_all(&mut self, out: &mut Vec<MidiMessage>) {
+        for note in self.pad_notes.drain(..) {
+            out.push(MidiMessage::note_off(self.channel, note));
+        }
+        if let Some(note) = self.hold_note.take() {
+            out.push(MidiMessage::note_off(self.channel, note));
+        }
+        self.flush_pluck_offs(out);
+    }
+
+    /// Release any pluck notes queued by a previous render call. Plucks are
+    /// one-shot, so their Note Off is deferred to the start of the
+    /// following call rather than held indefinitely like pad/hold voices.
+    fn flush_pluck_offs(&mut self, out: &mut Vec<MidiMessage>) {
+        for note in self.pending_pluck_offs.drain(..) {
+            out.push(MidiMessage::note_off(self.channel, note));
+        }
+    }
+
+    fn render_params(&mut self, params: &MusicParams, out: &mut Vec<MidiMessage>) {
+        let prev = self.last_params.unwrap_or(*params);
+
+        self.push_cc_if_changed(prev.brightness, params.brightness, cc::BRIGHTNESS, out);
+        self.push_cc_if_changed(prev.warmth, params.warmth, cc::WARMTH, out);
+        self.push_cc_if_changed(prev.reverb, params.reverb, cc::REVERB, out);
+        self.push_cc_if_changed(prev.width, params.width, cc::WIDTH, out);
+        self.push_cc_if_changed(prev.master, params.master, cc::MASTER, out);
+        self.push_cc_if_changed(prev.tension, params.tension, cc::TENSION, out);
+        self.push_cc_if_changed(prev.density, params.density, cc::DENSITY, out);
+
+        self.last_params = Some(*params);
+    }
+
+    fn push_cc_if_changed(&self, prev: f32, current: f32, controller: u8, out: &mut Vec<MidiMessage>) {
+        if self.last_params.is_none() || (current - prev).abs() > self.cc_threshold {
+            out.push(MidiMessage::control_change(
+                self.channel,
+                controller,
+                scale_velocity(current),
+            ));
+        }
+    }
+}
+
+/// Scale a 0..1 value to the 7-bit MIDI range (0..127).
+fn scale_velocity(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 127.0).round() as u8
+}
+
+/// Map a mode to a General MIDI program number, so a `Cadence`'s modulation
+/// can optionally select a different instrument patch on the receiving end.
+fn mode_to_program(mode: Mode) -> u8 {
+    match mode {
+        Mode::Major => 0,
+        Mode::Minor => 1,
+        Mode::Dorian => 2,
+        Mode::Mixolydian => 3,
+        Mode::Lydian => 4,
+        Mode::Phrygian => 5,
+        Mode::PentatonicMajor => 6,
+        Mode::PentatonicMinor => 7,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::HarmonyState;
+
+    fn frame_with_events(events: Vec<MusicEvent>) -> OutputFrame {
+        OutputFrame {
+            params: MusicParams::default(),
+            harmony: HarmonyState::default(),
+            events,
+            hold: None,
+            envelope_level: 0.0,
+            diagnostics: None,
+        }
+    }
+
+    #[test]
+    fn pluck_emits_note_on() {
+        let mut renderer = MidiRenderer::new(0);
+        let frame = frame_with_events(vec![MusicEvent::Pluck {
+            note: 60,
+            velocity: 1.0,
+            salience: 1.0,
+            pan: 0.0,
+        }]);
+
+        let messages = renderer.render(&frame);
+        assert!(messages
+            .iter()
+            .any(|m| m.status & 0xF0 == 0x90 && m.data1 == 60 && m.data2 == 127));
+    }
+
+    #[test]
+    fn pad_chord_change_releases_old_notes() {
+        let mut renderer = MidiRenderer::new(0);
+        let first = frame_with_events(vec![MusicEvent::PadChord {
+            notes: vec![60, 64, 67],
+            velocity: 0.5,
+            salience: 0.8,
+            pan: 0.0,
+        }]);
+        renderer.render(&first);
+
+        let second = frame_with_events(vec![MusicEvent::PadChord {
+            notes: vec![62, 65, 69],
+            velocity: 0.5,
+            salience: 0.8,
+            pan: 0.0,
+        }]);
+        let messages = renderer.render(&second);
+
+        assert!(messages
+            .iter()
+            .any(|m| m.status & 0xF0 == 0x80 && m.data1 == 60));
+        assert!(messages
+            .iter()
+            .any(|m| m.status & 0xF0 == 0x90 && m.data1 == 62));
+    }
+
+    #[test]
+    fn mute_releases_all_sounding_notes() {
+        let mut renderer = MidiRenderer::new(0);
+        let pad = frame_with_events(vec![MusicEvent::PadChord {
+            notes: vec![60, 64, 67],
+            velocity: 0.5,
+            salience: 0.8,
+            pan: 0.0,
+        }]);
+        renderer.render(&pad);
+
+        let mute = frame_with_events(vec![MusicEvent::Mute {
+            on: true,
+            salience: 1.0,
+        }]);
+        let messages = renderer.render(&mute);
+
+        let note_offs: Vec<u8> = messages
+            .iter()
+            .filter(|m| m.status & 0xF0 == 0x80)
+            .map(|m| m.data1)
+            .collect();
+        assert_eq!(note_offs.len(), 3);
+    }
+
+    #[test]
+    fn pluck_note_is_released_at_the_start_of_the_next_render_call() {
+        let mut renderer = MidiRenderer::new(0);
+        let pluck = frame_with_events(vec![MusicEvent::Pluck {
+            note: 60,
+            velocity: 1.0,
+            salience: 1.0,
+            pan: 0.0,
+        }]);
+        renderer.render(&pluck);
+
+        let next = frame_with_events(vec![]);
+        let messages = renderer.render(&next);
+        assert!(messages
+            .iter()
+            .any(|m| m.status & 0xF0 == 0x80 && m.data1 == 60));
+    }
+
+    #[test]
+    fn mute_releases_a_pending_pluck_note_immediately() {
+        let mut renderer = MidiRenderer::new(0);
+        let pluck = frame_with_events(vec![MusicEvent::Pluck {
+            note: 60,
+            velocity: 1.0,
+            salience: 1.0,
+            pan: 0.0,
+        }]);
+        renderer.render(&pluck);
+
+        let mute = frame_with_events(vec![MusicEvent::Mute {
+            on: true,
+            salience: 1.0,
+        }]);
+        let messages = renderer.render(&mute);
+        assert!(messages
+            .iter()
+            .any(|m| m.status & 0xF0 == 0x80 && m.data1 == 60));
+    }
+
+    #[test]
+    fn cadence_emits_program_change_only_when_enabled() {
+        let mut renderer = MidiRenderer::new(0);
+        let cadence = frame_with_events(vec![MusicEvent::Cadence {
+            to_root: 0,
+            to_mode: Mode::Dorian,
+            salience: 1.0,
+        }]);
+
+        let messages = renderer.render(&cadence);
+        assert!(messages.iter().all(|m| m.status & 0xF0 != 0xC0));
+
+        renderer.set_program_change_enabled(true);
+        let messages = renderer.render(&cadence);
+        assert!(messages
+            .iter()
+            .any(|m| m.status & 0xF0 == 0xC0 && m.data1 == mode_to_program(Mode::Dorian)));
+    }
+
+    #[test]
+    fn unchanged_params_do_not_repeat_cc() {
+        let mut renderer = MidiRenderer::new(0);
+        let frame = frame_with_events(vec![]);
+        renderer.render(&frame);
+
+        let messages = renderer.render(&frame);
+        assert!(messages.is_empty());
+    }
+}
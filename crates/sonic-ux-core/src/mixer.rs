@@ -0,0 +1,214 @@
+//! Submix stage applied to the engine's events just before they leave it.
+//!
+//! Gives front-ends a small mixer - per-event-category volume, pan, and
+//! mute/solo - so they can balance the relative loudness of interaction
+//! sounds without recompiling mapping constants, and save the result
+//! alongside a harmonic snapshot as a "soundscape" document.
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::{HoldState, MusicEvent};
+
+/// Per-category volume, pan, and mute/solo.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ChannelStrip {
+    /// Linear gain multiplier (1.0 = unity).
+    pub volume: f32,
+    /// Stereo placement (-1..1), 0 = center.
+    pub pan: f32,
+    pub mute: bool,
+    pub solo: bool,
+}
+
+impl Default for ChannelStrip {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            pan: 0.0,
+            mute: false,
+            solo: false,
+        }
+    }
+}
+
+/// A lightweight submix: one strip per event category plus a master level.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Mixer {
+    pub master_volume: f32,
+    pub plucks: ChannelStrip,
+    pub pad_chords: ChannelStrip,
+    pub accents: ChannelStrip,
+    pub hold: ChannelStrip,
+}
+
+impl Mixer {
+    /// Create a mixer with every strip at unity gain, unmuted, no solo.
+    pub fn new() -> Self {
+        Self {
+            master_volume: 1.0,
+            plucks: ChannelStrip::default(),
+            pad_chords: ChannelStrip::default(),
+            accents: ChannelStrip::default(),
+            hold: ChannelStrip::default(),
+        }
+    }
+
+    fn any_solo(&self) -> bool {
+        self.plucks.solo || self.pad_chords.solo || self.accents.solo || self.hold.solo
+    }
+
+    /// Apply per-category volume/pan/mute/solo to a frame's events.
+    pub fn apply(&self, events: Vec<MusicEvent>) -> Vec<MusicEvent> {
+        let solo_active = self.any_solo();
+        events
+            .into_iter()
+            .filter_map(|event| self.apply_event(event, solo_active))
+            .collect()
+    }
+
+    fn apply_event(&self, event: MusicEvent, solo_active: bool) -> Option<MusicEvent> {
+        match event {
+            MusicEvent::Pluck {
+                note,
+                velocity,
+                salience,
+                pan,
+            } => {
+                let (gain, strip_pan) = self.gain_and_pan(self.plucks, solo_active)?;
+                Some(MusicEvent::Pluck {
+                    note,
+                    velocity: (velocity * gain).clamp(0.0, 1.0),
+                    salience,
+                    pan: (pan + strip_pan).clamp(-1.0, 1.0),
+                })
+            }
+            MusicEvent::PadChord {
+                notes,
+                velocity,
+                salience,
+                pan,
+            } => {
+                let (gain, strip_pan) = self.gain_and_pan(self.pad_chords, solo_active)?;
+                Some(MusicEvent::PadChord {
+                    notes,
+                    velocity: (velocity * gain).clamp(0.0, 1.0),
+                    salience,
+                    pan: (pan + strip_pan).clamp(-1.0, 1.0),
+                })
+            }
+            MusicEvent::Accent {
+                strength,
+                salience,
+                pan,
+            } => {
+                let (gain, strip_pan) = self.gain_and_pan(self.accents, solo_active)?;
+                Some(MusicEvent::Accent {
+                    strength: (strength * gain).clamp(0.0, 1.0),
+                    salience,
+                    pan: (pan + strip_pan).clamp(-1.0, 1.0),
+                })
+            }
+            MusicEvent::PadVoiceOn {
+                note,
+                detune,
+                level,
+                pan,
+            } => {
+                let (gain, strip_pan) = self.gain_and_pan(self.pad_chords, solo_active)?;
+                Some(MusicEvent::PadVoiceOn {
+                    note,
+                    detune,
+                    level: (level * gain).clamp(0.0, 1.0),
+                    pan: (pan + strip_pan).clamp(-1.0, 1.0),
+                })
+            }
+            other => Some(other),
+        }
+    }
+
+    /// Apply the hold strip's volume to the click-and-hold voice.
+    pub fn apply_hold(&self, hold: Option<HoldState>) -> Option<HoldState> {
+        let hold = hold?;
+        let (gain, _pan) = self.gain_and_pan(self.hold, self.any_solo())?;
+        Some(HoldState {
+            note: hold.note,
+            velocity: (hold.velocity * gain).clamp(0.0, 1.0),
+        })
+    }
+
+    fn gain_and_pan(&self, strip: ChannelStrip, solo_active: bool) -> Option<(f32, f32)> {
+        if strip.mute || (solo_active && !strip.solo) {
+            None
+        } else {
+            Some((self.master_volume * strip.volume, strip.pan))
+        }
+    }
+}
+
+impl Default for Mixer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn muted_strip_drops_its_events() {
+        let mut mixer = Mixer::new();
+        mixer.plucks.mute = true;
+
+        let events = vec![MusicEvent::Pluck {
+            note: 60,
+            velocity: 1.0,
+            salience: 1.0,
+            pan: 0.0,
+        }];
+
+        assert!(mixer.apply(events).is_empty());
+    }
+
+    #[test]
+    fn solo_silences_other_categories() {
+        let mut mixer = Mixer::new();
+        mixer.accents.solo = true;
+
+        let events = vec![
+            MusicEvent::Pluck {
+                note: 60,
+                velocity: 1.0,
+                salience: 1.0,
+                pan: 0.0,
+            },
+            MusicEvent::Accent {
+                strength: 0.5,
+                salience: 0.5,
+                pan: 0.0,
+            },
+        ];
+
+        let result = mixer.apply(events);
+        assert_eq!(result.len(), 1);
+        assert!(matches!(result[0], MusicEvent::Accent { .. }));
+    }
+
+    #[test]
+    fn volume_scales_velocity() {
+        let mut mixer = Mixer::new();
+        mixer.plucks.volume = 0.5;
+
+        let events = vec![MusicEvent::Pluck {
+            note: 60,
+            velocity: 1.0,
+            salience: 1.0,
+            pan: 0.0,
+        }];
+
+        match &mixer.apply(events)[0] {
+            MusicEvent::Pluck { velocity, .. } => assert!((*velocity - 0.5).abs() < 0.001),
+            _ => panic!("expected pluck"),
+        }
+    }
+}
@@ -0,0 +1,738 @@
+//! Expressive phrasing applied to the raw event stream.
+//!
+//! `EventGenerator` emits musically "correct" but dynamically flat events -
+//! every accent the same loudness, every note the same length. This module
+//! shapes a phrase the way a performer would: a phrase opens once activity
+//! has risen for a few frames and its dynamics ramp across the events that
+//! follow; once activity decays the phrase closes and later events pass
+//! through unshaped, so a falling phrase gets no mirrored ramp. Articulation
+//! hints can shorten or lengthen the implied note durations.
+//!
+//! `Performance` plays the role of Euterpea's `Player`: it folds a rolling
+//! window of recent activity into per-event modifications. Two output
+//! shapes are offered - [`Performance::shape`] bakes the dynamics straight
+//! into the `MusicEvent` fields for callers (like `Engine`) that just want
+//! a shaped event back, while [`Performance::perform`] reports the
+//! velocity scale, timing offset and implied duration separately as a
+//! [`PerformedEvent`] for hosts that want full musical phrasing.
+
+use crate::harmony::HarmonyManager;
+use crate::types::MusicEvent;
+
+/// A musical inflection applied across a phrase window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PhraseAttribute {
+    /// Linear velocity ramp from `from` to `to` across the phrase.
+    Dynamics { from: f32, to: f32 },
+    /// Stretch (`> 1.0`) or compress (`< 1.0`) inter-event timing.
+    Tempo(f32),
+    /// Shortens or lengthens the implied note duration.
+    Articulation(Articulation),
+    /// Flat velocity boost applied on top of the dynamics ramp.
+    Accent(f32),
+    /// Velocity ramps from unity up to `1.0 + rate` across the phrase.
+    Crescendo(f32),
+    /// Velocity ramps from unity down to `1.0 - rate` across the phrase.
+    Diminuendo(f32),
+    /// Events are pulled earlier as the phrase progresses; `rate` is the
+    /// max pull, as a fraction of the base inter-event interval.
+    Accelerando(f32),
+    /// Events are pushed later as the phrase progresses; `rate` is the
+    /// max push, as a fraction of the base inter-event interval.
+    Ritardando(f32),
+    /// Shortens the implied note duration toward a floor, scaled by `rate`.
+    Staccato(f32),
+    /// Lengthens the implied note duration toward overlap with the next
+    /// onset, scaled by `rate`.
+    Legato(f32),
+}
+
+/// Articulation style affecting implied note duration and re-attacks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Articulation {
+    /// Shorten implied note durations.
+    Staccato,
+    /// Suppress re-attacks on repeated scale degrees.
+    Legato,
+}
+
+/// Direction a phrase is currently shaping toward.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PhraseDirection {
+    Crescendo,
+    Diminuendo,
+}
+
+/// A [`MusicEvent`] with its phrasing reported explicitly rather than
+/// baked into the event's own fields.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PerformedEvent {
+    pub event: MusicEvent,
+    /// Multiplier the host should apply to the event's velocity/salience.
+    pub velocity_scale: f32,
+    /// Scheduling offset from the event's nominal onset time, in ms.
+    pub time_offset_ms: i32,
+    /// Implied note duration, in ms.
+    pub duration_ms: u32,
+}
+
+/// A fully rendered note: pitch, scheduling and dynamics, produced by
+/// interpreting a closed-form sequence of scale degrees (as opposed to
+/// [`PerformedEvent`], which reports live phrasing for a stream of
+/// already-generated [`MusicEvent`]s).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PerfEvent {
+    pub pitch: u8,
+    /// Offset from the render's start time, in ms.
+    pub onset_ms: u32,
+    pub duration_ms: u32,
+    /// 0..1
+    pub velocity: f32,
+}
+
+/// Default implied note duration when no articulation is in effect.
+const DEFAULT_DURATION_MS: u32 = 220;
+/// Floor duration staccato shortens toward.
+const STACCATO_FLOOR_MS: u32 = 40;
+/// Duration legato lengthens toward, long enough to overlap the next onset.
+const LEGATO_DURATION_MS: u32 = 320;
+/// Base inter-event interval used to scale tempo-warp offsets.
+const BASE_INTERVAL_MS: f32 = 200.0;
+
+/// Rolling phrase context that shapes the events passing through it.
+///
+/// Holds a small window of recent activity so it can open a phrase when
+/// activity trends upward and close it when the trend decays, then scale
+/// each event's `velocity`/`salience` by a factor interpolated linearly
+/// across the phrase.
+#[derive(Debug)]
+pub struct Performance {
+    /// Activity level when the current phrase opened.
+    phrase_start_activity: f32,
+    /// Highest activity level seen during the current phrase.
+    phrase_peak_activity: f32,
+    /// Index of the current event within the phrase (0-based).
+    event_index: u32,
+    /// Expected length of the current phrase, in events.
+    expected_length: u32,
+    /// Direction the phrase is shaping toward.
+    direction: PhraseDirection,
+    /// Last-seen raw activity, used to detect rising/decaying trends.
+    last_activity: f32,
+    /// Consecutive frames of rising activity (opens a phrase).
+    rising_streak: u32,
+    /// Consecutive frames of decaying activity (closes a phrase).
+    decaying_streak: u32,
+    /// Whether a phrase is currently open.
+    phrase_open: bool,
+    /// Last pluck note seen, for legato re-attack suppression.
+    last_degree_note: Option<u8>,
+    /// Attributes in effect for the current phrase.
+    attributes: Vec<PhraseAttribute>,
+}
+
+/// Number of consecutive rising/decaying frames before a phrase opens/closes.
+const TREND_FRAMES: u32 = 4;
+/// Default expected phrase length, in events, when one isn't otherwise known.
+const DEFAULT_PHRASE_LENGTH: u32 = 8;
+/// Dynamic range multiplier used when `reduced_motion` is set (1.0 otherwise).
+const REDUCED_MOTION_RANGE: f32 = 0.3;
+
+impl Performance {
+    /// Create a performance context with no phrase open.
+    pub fn new() -> Self {
+        Self {
+            phrase_start_activity: 0.0,
+            phrase_peak_activity: 0.0,
+            event_index: 0,
+            expected_length: DEFAULT_PHRASE_LENGTH,
+            direction: PhraseDirection::Crescendo,
+            last_activity: 0.0,
+            rising_streak: 0,
+            decaying_streak: 0,
+            phrase_open: false,
+            last_degree_note: None,
+            attributes: Vec::new(),
+        }
+    }
+
+    /// Set the phrase attributes to apply while a phrase is open.
+    pub fn set_attributes(&mut self, attributes: Vec<PhraseAttribute>) {
+        self.attributes = attributes;
+    }
+
+    /// Reset phrasing state at a section boundary (cadence, new pad chord).
+    pub fn reset_phrase(&mut self) {
+        self.phrase_open = false;
+        self.event_index = 0;
+        self.rising_streak = 0;
+        self.decaying_streak = 0;
+    }
+
+    /// Advance the activity trend tracker for this frame.
+    fn track_activity(&mut self, activity: f32) {
+        if activity > self.last_activity + 0.01 {
+            self.rising_streak += 1;
+            self.decaying_streak = 0;
+        } else if activity < self.last_activity - 0.01 {
+            self.decaying_streak += 1;
+            self.rising_streak = 0;
+        }
+        self.last_activity = activity;
+
+        if !self.phrase_open && self.rising_streak >= TREND_FRAMES {
+            self.phrase_open = true;
+            self.phrase_start_activity = activity;
+            self.phrase_peak_activity = activity;
+            self.event_index = 0;
+            self.direction = PhraseDirection::Crescendo;
+        } else if self.phrase_open && self.decaying_streak >= TREND_FRAMES {
+            self.phrase_open = false;
+            self.direction = PhraseDirection::Diminuendo;
+        }
+
+        if self.phrase_open {
+            self.phrase_peak_activity = self.phrase_peak_activity.max(activity);
+        }
+    }
+
+    /// Shape a frame's events according to the current phrase context.
+    ///
+    /// `Cadence`/`PadChord` events reset the phrase boundary after shaping.
+    pub fn shape(
+        &mut self,
+        events: Vec<MusicEvent>,
+        activity: f32,
+        reduced_motion: bool,
+    ) -> Vec<MusicEvent> {
+        self.track_activity(activity);
+
+        let range = if reduced_motion {
+            REDUCED_MOTION_RANGE
+        } else {
+            1.0
+        };
+
+        let shaped = events
+            .into_iter()
+            .map(|event| {
+                let scaled = self.scale_event(event, range);
+                self.event_index += 1;
+                scaled
+            })
+            .collect::<Vec<_>>();
+
+        if shaped
+            .iter()
+            .any(|e| matches!(e, MusicEvent::Cadence { .. } | MusicEvent::PadChord { .. }))
+        {
+            self.reset_phrase();
+        }
+
+        shaped
+    }
+
+    /// Shape a frame's events into [`PerformedEvent`]s, reporting dynamics
+    /// and timing explicitly instead of baking them into the event.
+    ///
+    /// `Cadence`/`PadChord` events reset the phrase boundary after shaping,
+    /// same as [`Performance::shape`].
+    pub fn perform(
+        &mut self,
+        events: Vec<MusicEvent>,
+        activity: f32,
+        reduced_motion: bool,
+    ) -> Vec<PerformedEvent> {
+        self.track_activity(activity);
+
+        let range = if reduced_motion {
+            REDUCED_MOTION_RANGE
+        } else {
+            1.0
+        };
+
+        let performed = events
+            .into_iter()
+            .map(|event| {
+                let velocity_scale = self.velocity_scale(&event, range);
+                let time_offset_ms = self.time_offset_ms(range);
+                let duration_ms = self.duration_ms();
+                if let MusicEvent::Pluck { note, .. } = &event {
+                    self.last_degree_note = Some(*note);
+                }
+                self.event_index += 1;
+                PerformedEvent {
+                    event,
+                    velocity_scale,
+                    time_offset_ms,
+                    duration_ms,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        if performed.iter().any(|p| {
+            matches!(
+                p.event,
+                MusicEvent::Cadence { .. } | MusicEvent::PadChord { .. }
+            )
+        }) {
+            self.reset_phrase();
+        }
+
+        performed
+    }
+
+    /// Render a closed-form sequence of scale `degrees` into explicit note
+    /// events, using `harmony`'s current scale and the phrase attributes set
+    /// via [`Performance::set_attributes`].
+    ///
+    /// Unlike [`Performance::shape`]/[`Performance::perform`], this doesn't
+    /// consult the rolling activity-trend phrase tracker - the whole phrase
+    /// is known up front, so progress is `i / (degrees.len() - 1)` rather
+    /// than derived from a live event count. `dt_ms` is the nominal spacing
+    /// between successive degrees before tempo-warp attributes stretch or
+    /// compress it.
+    pub fn render(&self, harmony: &HarmonyManager, degrees: &[usize], dt_ms: f32) -> Vec<PerfEvent> {
+        let len = degrees.len();
+        if len == 0 {
+            return Vec::new();
+        }
+
+        let mut onset_ms = 0.0f32;
+        let mut events = Vec::with_capacity(len);
+
+        for (i, &degree) in degrees.iter().enumerate() {
+            let t = if len > 1 {
+                i as f32 / (len - 1) as f32
+            } else {
+                0.0
+            };
+            let slot_ms = self.onset_spacing_ms(dt_ms, t);
+
+            events.push(PerfEvent {
+                pitch: harmony.scale_note(degree, 4),
+                onset_ms: onset_ms.round().max(0.0) as u32,
+                duration_ms: self.articulated_duration_ms(slot_ms),
+                velocity: self.phrase_velocity(t).clamp(0.0, 1.0),
+            });
+
+            onset_ms += slot_ms;
+        }
+
+        events
+    }
+
+    /// Velocity multiplier for a rendered phrase at progress `t`, from the
+    /// dynamics-affecting attributes (`Dynamics`, `Accent`, `Crescendo`,
+    /// `Diminuendo`).
+    fn phrase_velocity(&self, t: f32) -> f32 {
+        let mut factor = 1.0;
+        for attr in &self.attributes {
+            match attr {
+                PhraseAttribute::Dynamics { from, to } => factor *= lerp(*from, *to, t),
+                PhraseAttribute::Accent(strength) => factor *= 1.0 + strength,
+                PhraseAttribute::Crescendo(rate) => factor *= 1.0 + lerp(0.0, *rate, t),
+                PhraseAttribute::Diminuendo(rate) => factor *= 1.0 - lerp(0.0, *rate, t),
+                _ => {}
+            }
+        }
+        factor.max(0.0)
+    }
+
+    /// Spacing (ms) until the next onset at progress `t`, from the
+    /// tempo-warping attributes (`Tempo`, `Accelerando`, `Ritardando`).
+    fn onset_spacing_ms(&self, dt_ms: f32, t: f32) -> f32 {
+        let mut spacing = dt_ms;
+        for attr in &self.attributes {
+            match attr {
+                PhraseAttribute::Tempo(stretch) => spacing *= stretch,
+                PhraseAttribute::Accelerando(rate) => spacing *= (1.0 - rate * t).max(0.1),
+                PhraseAttribute::Ritardando(rate) => spacing *= 1.0 + rate * t,
+                _ => {}
+            }
+        }
+        spacing.max(1.0)
+    }
+
+    /// Implied note duration (ms) scaled relative to `slot_ms`, the time
+    /// until the next onset, from the articulation attributes.
+    fn articulated_duration_ms(&self, slot_ms: f32) -> u32 {
+        let mut duration = slot_ms;
+        for attr in &self.attributes {
+            match attr {
+                PhraseAttribute::Articulation(Articulation::Staccato) => {
+                    duration = slot_ms * 0.3;
+                }
+                PhraseAttribute::Articulation(Articulation::Legato) => {
+                    duration = slot_ms * 1.3;
+                }
+                PhraseAttribute::Staccato(rate) => {
+                    duration = lerp(slot_ms, slot_ms * 0.3, rate.clamp(0.0, 1.0));
+                }
+                PhraseAttribute::Legato(rate) => {
+                    duration = lerp(slot_ms, slot_ms * 1.3, rate.clamp(0.0, 1.0));
+                }
+                _ => {}
+            }
+        }
+        duration.round().max(1.0) as u32
+    }
+
+    /// Compute the phrase progress factor `i / L`, clamped to `0..1`.
+    fn progress(&self) -> f32 {
+        (self.event_index as f32 / self.expected_length.max(1) as f32).clamp(0.0, 1.0)
+    }
+
+    /// Velocity multiplier contributed by the currently-open phrase's
+    /// dynamics-affecting attributes (`Dynamics`, `Accent`, `Crescendo`,
+    /// `Diminuendo`). Shared by [`Performance::scale_event`] and
+    /// [`Performance::perform`].
+    fn velocity_scale(&self, event: &MusicEvent, range: f32) -> f32 {
+        if !self.phrase_open {
+            return 1.0;
+        }
+        if self.is_legato_repeat_event(event) {
+            return 0.0;
+        }
+
+        let t = self.progress();
+        let mut factor = 1.0;
+        for attr in &self.attributes {
+            match attr {
+                PhraseAttribute::Dynamics { from, to } => {
+                    let (lo, hi) = match self.direction {
+                        PhraseDirection::Crescendo => (*from, *to),
+                        PhraseDirection::Diminuendo => (*to, *from),
+                    };
+                    let base = lerp(lo, hi, t);
+                    // Dampen the dynamic range around unity so shaping stays
+                    // subtle under reduced motion rather than being silenced.
+                    factor *= 1.0 + (base - 1.0) * range;
+                }
+                PhraseAttribute::Accent(strength) => {
+                    factor *= 1.0 + strength * range;
+                }
+                PhraseAttribute::Crescendo(rate) => {
+                    factor *= 1.0 + lerp(0.0, *rate, t) * range;
+                }
+                PhraseAttribute::Diminuendo(rate) => {
+                    factor *= 1.0 - lerp(0.0, *rate, t) * range;
+                }
+                PhraseAttribute::Tempo(_)
+                | PhraseAttribute::Articulation(_)
+                | PhraseAttribute::Accelerando(_)
+                | PhraseAttribute::Ritardando(_)
+                | PhraseAttribute::Staccato(_)
+                | PhraseAttribute::Legato(_) => {
+                    // Timing/duration shaping, handled separately.
+                }
+            }
+        }
+        factor.max(0.0)
+    }
+
+    /// Scheduling offset contributed by tempo-warping attributes (`Tempo`,
+    /// `Accelerando`, `Ritardando`), in ms relative to the event's nominal
+    /// onset.
+    fn time_offset_ms(&self, range: f32) -> i32 {
+        if !self.phrase_open {
+            return 0;
+        }
+
+        let t = self.progress();
+        let mut offset = 0.0;
+        for attr in &self.attributes {
+            match attr {
+                PhraseAttribute::Tempo(stretch) => {
+                    offset += (stretch - 1.0) * BASE_INTERVAL_MS * t * range;
+                }
+                PhraseAttribute::Accelerando(rate) => {
+                    offset -= rate * BASE_INTERVAL_MS * t * range;
+                }
+                PhraseAttribute::Ritardando(rate) => {
+                    offset += rate * BASE_INTERVAL_MS * t * range;
+                }
+                _ => {}
+            }
+        }
+        offset.round() as i32
+    }
+
+    /// Implied note duration contributed by articulation attributes
+    /// (`Articulation`, `Staccato`, `Legato`).
+    fn duration_ms(&self) -> u32 {
+        let mut duration = DEFAULT_DURATION_MS as f32;
+        for attr in &self.attributes {
+            match attr {
+                PhraseAttribute::Articulation(Articulation::Staccato) => {
+                    duration = lerp(duration, STACCATO_FLOOR_MS as f32, 1.0);
+                }
+                PhraseAttribute::Articulation(Articulation::Legato) => {
+                    duration = lerp(duration, LEGATO_DURATION_MS as f32, 1.0);
+                }
+                PhraseAttribute::Staccato(rate) => {
+                    duration = lerp(duration, STACCATO_FLOOR_MS as f32, rate.clamp(0.0, 1.0));
+                }
+                PhraseAttribute::Legato(rate) => {
+                    duration = lerp(duration, LEGATO_DURATION_MS as f32, rate.clamp(0.0, 1.0));
+                }
+                _ => {}
+            }
+        }
+        duration.round().max(1.0) as u32
+    }
+
+    fn scale_event(&mut self, event: MusicEvent, range: f32) -> MusicEvent {
+        let factor = self.velocity_scale(&event, range);
+
+        match event {
+            MusicEvent::Pluck {
+                note,
+                velocity,
+                salience,
+                pan,
+            } => {
+                if self.is_legato_repeat(note) {
+                    return MusicEvent::Pluck {
+                        note,
+                        velocity: 0.0,
+                        salience: 0.0,
+                        pan,
+                    };
+                }
+                self.last_degree_note = Some(note);
+                MusicEvent::Pluck {
+                    note,
+                    velocity: (velocity * factor).clamp(0.0, 1.0),
+                    salience,
+                    pan,
+                }
+            }
+            MusicEvent::PadChord {
+                notes,
+                velocity,
+                salience,
+                pan,
+            } => MusicEvent::PadChord {
+                notes,
+                velocity: (velocity * factor).clamp(0.0, 1.0),
+                salience,
+                pan,
+            },
+            MusicEvent::Accent {
+                strength,
+                salience,
+                pan,
+            } => MusicEvent::Accent {
+                strength: (strength * factor).clamp(0.0, 1.0),
+                salience,
+                pan,
+            },
+            other => other,
+        }
+    }
+
+    fn is_legato_repeat(&self, note: u8) -> bool {
+        self.attributes.iter().any(|a| {
+            matches!(a, PhraseAttribute::Articulation(Articulation::Legato))
+                || matches!(a, PhraseAttribute::Legato(_))
+        }) && self.last_degree_note == Some(note)
+    }
+
+    fn is_legato_repeat_event(&self, event: &MusicEvent) -> bool {
+        match event {
+            MusicEvent::Pluck { note, .. } => self.is_legato_repeat(*note),
+            _ => false,
+        }
+    }
+}
+
+impl Default for Performance {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dynamics_ramp_quietens_phrase_start() {
+        let mut perf = Performance::new();
+        perf.set_attributes(vec![PhraseAttribute::Dynamics { from: 0.2, to: 1.0 }]);
+
+        // Rise activity for TREND_FRAMES to open a phrase.
+        for a in [0.2, 0.4, 0.6, 0.8] {
+            perf.shape(Vec::new(), a, false);
+        }
+
+        let first = perf.shape(
+            vec![MusicEvent::Pluck {
+                note: 60,
+                velocity: 1.0,
+                salience: 1.0,
+                pan: 0.0,
+            }],
+            0.8,
+            false,
+        );
+        match &first[0] {
+            MusicEvent::Pluck { velocity, .. } => assert!(*velocity < 1.0),
+            _ => panic!("expected pluck"),
+        }
+    }
+
+    #[test]
+    fn reduced_motion_clamps_range() {
+        let mut perf = Performance::new();
+        perf.set_attributes(vec![PhraseAttribute::Accent(1.0)]);
+        for a in [0.2, 0.4, 0.6, 0.8] {
+            perf.shape(Vec::new(), a, true);
+        }
+        let events = perf.shape(
+            vec![MusicEvent::Accent {
+                strength: 0.5,
+                salience: 0.5,
+                pan: 0.0,
+            }],
+            0.8,
+            true,
+        );
+        match &events[0] {
+            MusicEvent::Accent { strength, .. } => {
+                assert!(*strength < 0.5 * (1.0 + 1.0))
+            }
+            _ => panic!("expected accent"),
+        }
+    }
+
+    #[test]
+    fn cadence_resets_phrase() {
+        let mut perf = Performance::new();
+        for a in [0.2, 0.4, 0.6, 0.8] {
+            perf.shape(Vec::new(), a, false);
+        }
+        assert!(perf.phrase_open);
+        perf.shape(
+            vec![MusicEvent::Cadence {
+                to_root: 0,
+                to_mode: crate::types::Mode::Major,
+                salience: 1.0,
+            }],
+            0.8,
+            false,
+        );
+        assert!(!perf.phrase_open);
+    }
+
+    #[test]
+    fn crescendo_attribute_raises_velocity_scale_over_the_phrase() {
+        let mut perf = Performance::new();
+        perf.set_attributes(vec![PhraseAttribute::Crescendo(0.5)]);
+        for a in [0.2, 0.4, 0.6, 0.8] {
+            perf.perform(Vec::new(), a, false);
+        }
+        let early = perf.velocity_scale(
+            &MusicEvent::Pluck {
+                note: 60,
+                velocity: 1.0,
+                salience: 1.0,
+                pan: 0.0,
+            },
+            1.0,
+        );
+        perf.event_index = perf.expected_length;
+        let late = perf.velocity_scale(
+            &MusicEvent::Pluck {
+                note: 62,
+                velocity: 1.0,
+                salience: 1.0,
+                pan: 0.0,
+            },
+            1.0,
+        );
+        assert!(late > early);
+    }
+
+    #[test]
+    fn staccato_attribute_shortens_duration_toward_floor() {
+        let mut perf = Performance::new();
+        perf.set_attributes(vec![PhraseAttribute::Staccato(1.0)]);
+        assert_eq!(perf.duration_ms(), STACCATO_FLOOR_MS);
+    }
+
+    #[test]
+    fn accelerando_pulls_events_earlier_as_phrase_progresses() {
+        let mut perf = Performance::new();
+        perf.set_attributes(vec![PhraseAttribute::Accelerando(1.0)]);
+        for a in [0.2, 0.4, 0.6, 0.8] {
+            perf.perform(Vec::new(), a, false);
+        }
+        perf.event_index = perf.expected_length;
+        let offset = perf.time_offset_ms(1.0);
+        assert!(offset < 0);
+    }
+
+    #[test]
+    fn perform_returns_performed_events_with_default_duration() {
+        let mut perf = Performance::new();
+        let performed = perf.perform(
+            vec![MusicEvent::Accent {
+                strength: 0.5,
+                salience: 0.5,
+                pan: 0.0,
+            }],
+            0.0,
+            false,
+        );
+        assert_eq!(performed.len(), 1);
+        assert_eq!(performed[0].duration_ms, DEFAULT_DURATION_MS);
+        assert_eq!(performed[0].time_offset_ms, 0);
+    }
+
+    #[test]
+    fn render_shapes_a_rising_dynamics_phrase_into_perf_events() {
+        use crate::harmony::{HarmonyManager, Preset};
+
+        let harmony = HarmonyManager::new(1, Preset::Ambient);
+        let mut perf = Performance::new();
+        perf.set_attributes(vec![PhraseAttribute::Dynamics { from: 0.2, to: 1.0 }]);
+
+        let events = perf.render(&harmony, &[0, 2, 4, 0], 200.0);
+
+        assert_eq!(events.len(), 4);
+        assert!(events[0].velocity < events[3].velocity);
+        assert_eq!(events[0].onset_ms, 0);
+        assert!(events[1].onset_ms > events[0].onset_ms);
+    }
+
+    #[test]
+    fn render_scales_duration_by_articulation() {
+        use crate::harmony::{HarmonyManager, Preset};
+
+        let harmony = HarmonyManager::new(1, Preset::Ambient);
+
+        let mut staccato = Performance::new();
+        staccato.set_attributes(vec![PhraseAttribute::Articulation(Articulation::Staccato)]);
+        let mut legato = Performance::new();
+        legato.set_attributes(vec![PhraseAttribute::Articulation(Articulation::Legato)]);
+
+        let staccato_events = staccato.render(&harmony, &[0, 2], 200.0);
+        let legato_events = legato.render(&harmony, &[0, 2], 200.0);
+
+        assert!(staccato_events[0].duration_ms < legato_events[0].duration_ms);
+    }
+
+    #[test]
+    fn each_preset_supplies_phrase_attributes() {
+        use crate::harmony::Preset;
+
+        assert!(!Preset::Ambient.phrase_attributes().is_empty());
+        assert!(!Preset::Playful.phrase_attributes().is_empty());
+    }
+}
@@ -1,6 +1,30 @@
 //! Parameter smoothing for anti-zipper and natural decay.
 //!
-//! Provides configurable attack/release curves to prevent harsh transitions.
+//! Provides configurable attack/release curves to prevent harsh transitions,
+//! a perceptual loudness curve for `master`/`density`, and per-event ADSR
+//! envelopes that decay/release naturally instead of stepping.
+
+use serde::{Deserialize, Serialize};
+
+/// How far `current` is allowed to be from `target` before a [`SmoothedParam`]
+/// reports itself as [`SmoothedParam::is_settled`].
+const SETTLE_EPSILON: f32 = 0.001;
+
+/// Curve a [`SmoothedParam`] follows while moving `current` toward `target`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SmoothingStyle {
+    /// Constant-rate ramp: moves by a fixed amount per step, reaching
+    /// `target` exactly rather than asymptotically.
+    Linear,
+    /// One-pole ramp: moves by a fixed fraction of the remaining distance
+    /// per step. The original, and still default, behavior.
+    #[default]
+    Exponential,
+    /// Like `Exponential` but with a steeper initial approach, for moves
+    /// that should feel like they "arrive" sooner even though they still
+    /// settle asymptotically.
+    Logarithmic,
+}
 
 /// Smoothed parameter with configurable attack/release.
 #[derive(Debug, Clone, Copy)]
@@ -13,6 +37,8 @@ pub struct SmoothedParam {
     attack: f32,
     /// Release coefficient (0..1, higher = faster)
     release: f32,
+    /// Curve used to advance `current` toward `target`
+    style: SmoothingStyle,
 }
 
 impl SmoothedParam {
@@ -24,6 +50,7 @@ impl SmoothedParam {
             target: initial,
             attack: 0.05,   // Slow attack
             release: 0.02,  // Even slower release
+            style: SmoothingStyle::Exponential,
         }
     }
 
@@ -34,6 +61,21 @@ impl SmoothedParam {
             target: initial,
             attack: attack.clamp(0.001, 1.0),
             release: release.clamp(0.001, 1.0),
+            style: SmoothingStyle::Exponential,
+        }
+    }
+
+    /// Create with attack/release expressed as real time constants (ms),
+    /// converted to per-step coefficients for the given update rate, so the
+    /// smoothing follows those time constants when `update()`/`next_block()`
+    /// advance `update_hz` steps per second.
+    pub fn with_time(initial: f32, attack_ms: f32, release_ms: f32, update_hz: f32) -> Self {
+        Self {
+            current: initial,
+            target: initial,
+            attack: time_to_coefficient(attack_ms, update_hz),
+            release: time_to_coefficient(release_ms, update_hz),
+            style: SmoothingStyle::Exponential,
         }
     }
 
@@ -42,6 +84,14 @@ impl SmoothedParam {
         self.target = target;
     }
 
+    /// Jump the current value directly to `value`, bypassing attack/release.
+    /// Used when restoring a saved snapshot, where the value shouldn't have
+    /// to re-converge from wherever the smoother last was.
+    pub fn set_value(&mut self, value: f32) {
+        self.current = value;
+        self.target = value;
+    }
+
     /// Get the current smoothed value.
     pub fn value(&self) -> f32 {
         self.current
@@ -52,19 +102,40 @@ impl SmoothedParam {
         self.target
     }
 
-    /// Update the smoothed value (call once per frame).
+    /// Update the smoothed value (call once per frame/sample).
     pub fn update(&mut self) {
-        let coeff = if self.target > self.current {
-            self.attack
-        } else {
-            self.release
-        };
-        self.current = lerp(self.current, self.target, coeff);
+        let coeff = self.active_coefficient();
+        self.current = advance(self.current, self.target, coeff, self.style);
+    }
+
+    /// Fill `out` with successive smoothed values, one `update()` per slot.
+    /// Lets block-based audio consumers avoid a per-sample method call.
+    pub fn next_block(&mut self, out: &mut [f32]) {
+        for sample in out.iter_mut() {
+            self.update();
+            *sample = self.current;
+        }
     }
 
     /// Check if the value has effectively reached its target.
     pub fn is_settled(&self) -> bool {
-        (self.current - self.target).abs() < 0.001
+        (self.current - self.target).abs() < SETTLE_EPSILON
+    }
+
+    /// Estimate how many more `update()` steps until `is_settled()` would be
+    /// true, given the current coefficient and style.
+    pub fn steps_left(&self) -> usize {
+        let diff = (self.target - self.current).abs();
+        if diff < SETTLE_EPSILON {
+            return 0;
+        }
+
+        let coeff = self.active_coefficient();
+        match self.style {
+            SmoothingStyle::Linear => (diff / coeff.max(0.0001)).ceil() as usize,
+            SmoothingStyle::Exponential => exponential_steps_left(diff, coeff),
+            SmoothingStyle::Logarithmic => exponential_steps_left(diff, coeff.sqrt()),
+        }
     }
 
     /// Set attack coefficient.
@@ -76,6 +147,36 @@ impl SmoothedParam {
     pub fn set_release(&mut self, release: f32) {
         self.release = release.clamp(0.001, 1.0);
     }
+
+    /// Get the attack coefficient.
+    pub fn attack(&self) -> f32 {
+        self.attack
+    }
+
+    /// Get the release coefficient.
+    pub fn release(&self) -> f32 {
+        self.release
+    }
+
+    /// Get the curve used to advance toward the target.
+    pub fn style(&self) -> SmoothingStyle {
+        self.style
+    }
+
+    /// Set the curve used to advance toward the target.
+    pub fn set_style(&mut self, style: SmoothingStyle) {
+        self.style = style;
+    }
+
+    /// The attack or release coefficient, whichever direction `current` is
+    /// currently moving in.
+    fn active_coefficient(&self) -> f32 {
+        if self.target > self.current {
+            self.attack
+        } else {
+            self.release
+        }
+    }
 }
 
 impl Default for SmoothedParam {
@@ -84,35 +185,134 @@ impl Default for SmoothedParam {
     }
 }
 
+/// Advance `current` toward `target` by one step of `coeff` under `style`.
+fn advance(current: f32, target: f32, coeff: f32, style: SmoothingStyle) -> f32 {
+    match style {
+        SmoothingStyle::Linear => {
+            let diff = target - current;
+            let step = coeff.abs();
+            if diff.abs() <= step {
+                target
+            } else {
+                current + step * diff.signum()
+            }
+        }
+        SmoothingStyle::Exponential => lerp(current, target, coeff),
+        SmoothingStyle::Logarithmic => lerp(current, target, coeff.sqrt().min(1.0)),
+    }
+}
+
+/// Convert a time constant (ms) and update rate (Hz) to a per-step
+/// exponential coefficient.
+fn time_to_coefficient(time_ms: f32, update_hz: f32) -> f32 {
+    let time_ms = time_ms.max(0.001);
+    let update_hz = update_hz.max(1.0);
+    (1.0 - (-1.0 / (time_ms * 0.001 * update_hz)).exp()).clamp(0.001, 1.0)
+}
+
+/// Number of geometric-decay steps until `diff` decays below
+/// [`SETTLE_EPSILON`] at the given per-step `coeff`.
+fn exponential_steps_left(diff: f32, coeff: f32) -> usize {
+    let ratio = (1.0 - coeff).clamp(0.0001, 0.9999);
+    (((SETTLE_EPSILON / diff).ln()) / ratio.ln()).ceil().max(1.0) as usize
+}
+
 /// Smoother for all musical parameters.
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct ParamSmoother {
-    pub cutoff: SmoothedParam,
+    pub master: SmoothedParam,
     pub warmth: SmoothedParam,
-    pub stereo_width: SmoothedParam,
+    pub brightness: SmoothedParam,
+    pub width: SmoothedParam,
+    pub motion: SmoothedParam,
     pub reverb: SmoothedParam,
-    pub activity: SmoothedParam,
+    pub density: SmoothedParam,
+    pub tension: SmoothedParam,
+    pub pan: SmoothedParam,
+    /// Optional envelope-driven dynamics for `density`, in place of plain
+    /// smoothing. `None` (the default) preserves the original behavior.
+    density_envelope: Option<Envelope>,
+    /// Optional envelope-driven dynamics for `reverb`, in place of plain
+    /// smoothing. `None` (the default) preserves the original behavior.
+    reverb_envelope: Option<Envelope>,
 }
 
 impl ParamSmoother {
     /// Create a new param smoother with default values.
     pub fn new() -> Self {
         Self {
-            cutoff: SmoothedParam::new(0.5),
-            warmth: SmoothedParam::new(0.5),
-            stereo_width: SmoothedParam::new(0.3),
-            reverb: SmoothedParam::new(0.4),
-            activity: SmoothedParam::new(0.0),
+            master: SmoothedParam::new(0.55),
+            warmth: SmoothedParam::new(0.3),
+            brightness: SmoothedParam::new(0.5),
+            width: SmoothedParam::new(0.3),
+            motion: SmoothedParam::new(0.0),
+            reverb: SmoothedParam::new(0.2),
+            density: SmoothedParam::new(0.0),
+            tension: SmoothedParam::new(0.3),
+            pan: SmoothedParam::new(0.0),
+            density_envelope: None,
+            reverb_envelope: None,
         }
     }
 
-    /// Update all smoothed parameters.
-    pub fn update(&mut self) {
-        self.cutoff.update();
+    /// Drive `density` from an ADSR envelope instead of plain smoothing,
+    /// gated by [`ParamSmoother::gate_envelopes`]. Gives note-like dynamics
+    /// to busy/idle activity swings rather than a one-pole ramp.
+    pub fn enable_density_envelope(&mut self, timing: EnvelopeTiming) {
+        self.density_envelope = Some(Envelope::new(timing));
+    }
+
+    /// Return `density` to plain smoothing.
+    pub fn disable_density_envelope(&mut self) {
+        self.density_envelope = None;
+    }
+
+    /// Drive `reverb` from an ADSR envelope instead of plain smoothing,
+    /// gated by [`ParamSmoother::gate_envelopes`].
+    pub fn enable_reverb_envelope(&mut self, timing: EnvelopeTiming) {
+        self.reverb_envelope = Some(Envelope::new(timing));
+    }
+
+    /// Return `reverb` to plain smoothing.
+    pub fn disable_reverb_envelope(&mut self) {
+        self.reverb_envelope = None;
+    }
+
+    /// Trigger `note_on`/`note_off` on every enabled envelope, e.g. when
+    /// interaction activity crosses a "sounding" threshold.
+    pub fn gate_envelopes(&mut self, active: bool) {
+        for envelope in [self.density_envelope.as_mut(), self.reverb_envelope.as_mut()]
+            .into_iter()
+            .flatten()
+        {
+            if active {
+                envelope.note_on();
+            } else {
+                envelope.note_off();
+            }
+        }
+    }
+
+    /// Update all smoothed parameters. `dt_ms` only matters to params
+    /// currently driven by an envelope; plain-smoothed params still advance
+    /// one fixed step per call as before.
+    pub fn update(&mut self, dt_ms: f32) {
+        self.master.update();
         self.warmth.update();
-        self.stereo_width.update();
+        self.brightness.update();
+        self.width.update();
+        self.motion.update();
         self.reverb.update();
-        self.activity.update();
+        self.density.update();
+        self.tension.update();
+        self.pan.update();
+
+        if let Some(envelope) = self.density_envelope.as_mut() {
+            self.density.set_value(envelope.tick(dt_ms));
+        }
+        if let Some(envelope) = self.reverb_envelope.as_mut() {
+            self.reverb.set_value(envelope.tick(dt_ms));
+        }
     }
 
     /// Apply reduced motion profile - increases smoothing times.
@@ -120,16 +320,10 @@ impl ParamSmoother {
         let slow_attack = 0.02;
         let slow_release = 0.01;
 
-        self.cutoff.set_attack(slow_attack);
-        self.cutoff.set_release(slow_release);
-        self.warmth.set_attack(slow_attack);
-        self.warmth.set_release(slow_release);
-        self.stereo_width.set_attack(slow_attack);
-        self.stereo_width.set_release(slow_release);
-        self.reverb.set_attack(slow_attack);
-        self.reverb.set_release(slow_release);
-        self.activity.set_attack(slow_attack);
-        self.activity.set_release(slow_release);
+        for param in self.params_mut() {
+            param.set_attack(slow_attack);
+            param.set_release(slow_release);
+        }
     }
 
     /// Apply normal smoothing profile.
@@ -137,19 +331,414 @@ impl ParamSmoother {
         let normal_attack = 0.05;
         let normal_release = 0.02;
 
-        self.cutoff.set_attack(normal_attack);
-        self.cutoff.set_release(normal_release);
-        self.warmth.set_attack(normal_attack);
-        self.warmth.set_release(normal_release);
-        self.stereo_width.set_attack(normal_attack);
-        self.stereo_width.set_release(normal_release);
-        self.reverb.set_attack(normal_attack);
-        self.reverb.set_release(normal_release);
-        self.activity.set_attack(normal_attack);
-        self.activity.set_release(normal_release);
+        for param in self.params_mut() {
+            param.set_attack(normal_attack);
+            param.set_release(normal_release);
+        }
+    }
+
+    /// The master param's attack coefficient, reported as a representative
+    /// value for diagnostics.
+    pub fn attack(&self) -> f32 {
+        self.master.attack()
+    }
+
+    /// The master param's release coefficient, reported as a representative
+    /// value for diagnostics.
+    pub fn release(&self) -> f32 {
+        self.master.release()
+    }
+
+    fn params_mut(&mut self) -> [&mut SmoothedParam; 9] {
+        [
+            &mut self.master,
+            &mut self.warmth,
+            &mut self.brightness,
+            &mut self.width,
+            &mut self.motion,
+            &mut self.reverb,
+            &mut self.density,
+            &mut self.tension,
+            &mut self.pan,
+        ]
+    }
+
+    /// Capture every param's (attack, release) coefficients for snapshotting.
+    pub fn coefficients(&self) -> SmoothingSnapshot {
+        SmoothingSnapshot {
+            master: (self.master.attack(), self.master.release()),
+            warmth: (self.warmth.attack(), self.warmth.release()),
+            brightness: (self.brightness.attack(), self.brightness.release()),
+            width: (self.width.attack(), self.width.release()),
+            motion: (self.motion.attack(), self.motion.release()),
+            reverb: (self.reverb.attack(), self.reverb.release()),
+            density: (self.density.attack(), self.density.release()),
+            tension: (self.tension.attack(), self.tension.release()),
+            pan: (self.pan.attack(), self.pan.release()),
+        }
+    }
+
+    /// Restore every param's (attack, release) coefficients from a snapshot.
+    pub fn restore_coefficients(&mut self, snapshot: SmoothingSnapshot) {
+        self.master.set_attack(snapshot.master.0);
+        self.master.set_release(snapshot.master.1);
+        self.warmth.set_attack(snapshot.warmth.0);
+        self.warmth.set_release(snapshot.warmth.1);
+        self.brightness.set_attack(snapshot.brightness.0);
+        self.brightness.set_release(snapshot.brightness.1);
+        self.width.set_attack(snapshot.width.0);
+        self.width.set_release(snapshot.width.1);
+        self.motion.set_attack(snapshot.motion.0);
+        self.motion.set_release(snapshot.motion.1);
+        self.reverb.set_attack(snapshot.reverb.0);
+        self.reverb.set_release(snapshot.reverb.1);
+        self.density.set_attack(snapshot.density.0);
+        self.density.set_release(snapshot.density.1);
+        self.tension.set_attack(snapshot.tension.0);
+        self.tension.set_release(snapshot.tension.1);
+        self.pan.set_attack(snapshot.pan.0);
+        self.pan.set_release(snapshot.pan.1);
+    }
+}
+
+/// A capture of every `ParamSmoother` field's (attack, release) coefficients,
+/// for persisting and restoring exactly via [`EngineSnapshot`](crate::snapshot::EngineSnapshot).
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SmoothingSnapshot {
+    pub master: (f32, f32),
+    pub warmth: (f32, f32),
+    pub brightness: (f32, f32),
+    pub width: (f32, f32),
+    pub motion: (f32, f32),
+    pub reverb: (f32, f32),
+    pub density: (f32, f32),
+    pub tension: (f32, f32),
+    pub pan: (f32, f32),
+}
+
+impl Default for ParamSmoother {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Perceptual loudness curve applied to `master`/`density` so equal numeric
+/// steps sound equal-loud, instead of a raw linear 0..1 value producing
+/// abrupt-sounding onsets.
+///
+/// `amp = 10^((level - 1) * range_db / 20)`, so `level = 1.0` maps to unity
+/// gain and `level = 0.0` maps to `-range_db` dB.
+#[derive(Debug, Clone, Copy)]
+pub struct VolumeTable {
+    range_db: f32,
+}
+
+impl VolumeTable {
+    /// Create a volume table with the given dB range below unity gain.
+    pub fn new(range_db: f32) -> Self {
+        Self {
+            range_db: range_db.max(0.0),
+        }
+    }
+
+    /// Map a linear 0..1 level to perceptual amplitude (also 0..1).
+    pub fn amplitude(&self, level: f32) -> f32 {
+        let level = level.clamp(0.0, 1.0);
+        10f32.powf((level - 1.0) * self.range_db / 20.0)
+    }
+}
+
+impl Default for VolumeTable {
+    fn default() -> Self {
+        Self::new(60.0)
+    }
+}
+
+/// Attack/decay/sustain/release timing (ms) for a [`TriggerEnvelope`].
+#[derive(Debug, Clone, Copy)]
+pub struct EnvelopeSettings {
+    pub attack_ms: f32,
+    pub decay_ms: f32,
+    /// Sustain level, as a fraction (0..1) of the envelope's peak.
+    pub sustain: f32,
+    /// How long the sustain plateau holds before releasing.
+    pub sustain_hold_ms: f32,
+    pub release_ms: f32,
+}
+
+impl Default for EnvelopeSettings {
+    fn default() -> Self {
+        Self {
+            attack_ms: 5.0,
+            decay_ms: 80.0,
+            sustain: 0.5,
+            sustain_hold_ms: 120.0,
+            release_ms: 250.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EnvelopeStage {
+    Attack,
+    Decay,
+    Sustain,
+    Release,
+    Done,
+}
+
+/// A one-shot ADSR envelope spawned by a triggered `Pluck`/`PadChord`.
+///
+/// Runs attack -> decay -> a fixed sustain hold -> release on its own once
+/// triggered, rather than waiting on an explicit note-off, since transient
+/// events don't carry one. [`EnvelopePool`] sums several of these together.
+#[derive(Debug, Clone, Copy)]
+pub struct TriggerEnvelope {
+    settings: EnvelopeSettings,
+    peak: f32,
+    stage: EnvelopeStage,
+    stage_elapsed_ms: f32,
+    level: f32,
+}
+
+impl TriggerEnvelope {
+    /// Trigger a new envelope that rises to `peak` (0..1).
+    pub fn trigger(settings: EnvelopeSettings, peak: f32) -> Self {
+        Self {
+            settings,
+            peak: peak.clamp(0.0, 1.0),
+            stage: EnvelopeStage::Attack,
+            stage_elapsed_ms: 0.0,
+            level: 0.0,
+        }
+    }
+
+    /// Advance the envelope by `dt_ms` and return its instantaneous value.
+    pub fn advance(&mut self, dt_ms: f32) -> f32 {
+        self.stage_elapsed_ms += dt_ms;
+
+        match self.stage {
+            EnvelopeStage::Attack => {
+                let t = (self.stage_elapsed_ms / self.settings.attack_ms.max(1.0)).min(1.0);
+                self.level = self.peak * t;
+                if t >= 1.0 {
+                    self.stage = EnvelopeStage::Decay;
+                    self.stage_elapsed_ms = 0.0;
+                }
+            }
+            EnvelopeStage::Decay => {
+                let t = (self.stage_elapsed_ms / self.settings.decay_ms.max(1.0)).min(1.0);
+                self.level = lerp(self.peak, self.peak * self.settings.sustain, t);
+                if t >= 1.0 {
+                    self.stage = EnvelopeStage::Sustain;
+                    self.stage_elapsed_ms = 0.0;
+                }
+            }
+            EnvelopeStage::Sustain => {
+                self.level = self.peak * self.settings.sustain;
+                if self.stage_elapsed_ms >= self.settings.sustain_hold_ms {
+                    self.stage = EnvelopeStage::Release;
+                    self.stage_elapsed_ms = 0.0;
+                }
+            }
+            EnvelopeStage::Release => {
+                let start = self.peak * self.settings.sustain;
+                let t = (self.stage_elapsed_ms / self.settings.release_ms.max(1.0)).min(1.0);
+                self.level = lerp(start, 0.0, t);
+                if t >= 1.0 {
+                    self.stage = EnvelopeStage::Done;
+                    self.level = 0.0;
+                }
+            }
+            EnvelopeStage::Done => {
+                self.level = 0.0;
+            }
+        }
+
+        self.level
+    }
+
+    /// Whether the envelope has fully released.
+    pub fn is_finished(&self) -> bool {
+        self.stage == EnvelopeStage::Done
+    }
+}
+
+/// A pool of concurrently-running [`TriggerEnvelope`]s, summed into a
+/// single instantaneous value. Finished envelopes are pruned automatically.
+#[derive(Debug, Default)]
+pub struct EnvelopePool {
+    envelopes: Vec<TriggerEnvelope>,
+}
+
+impl EnvelopePool {
+    /// Create an empty envelope pool.
+    pub fn new() -> Self {
+        Self {
+            envelopes: Vec::new(),
+        }
+    }
+
+    /// Spawn a new envelope rising to `peak` (0..1).
+    pub fn spawn(&mut self, settings: EnvelopeSettings, peak: f32) {
+        self.envelopes.push(TriggerEnvelope::trigger(settings, peak));
+    }
+
+    /// Advance every envelope by `dt_ms`, pruning finished ones, and return
+    /// their summed instantaneous value.
+    pub fn advance(&mut self, dt_ms: f32) -> f32 {
+        let mut sum = 0.0;
+        self.envelopes.retain_mut(|envelope| {
+            sum += envelope.advance(dt_ms);
+            !envelope.is_finished()
+        });
+        sum
+    }
+
+    /// Number of envelopes still running.
+    pub fn active_count(&self) -> usize {
+        self.envelopes.len()
+    }
+}
+
+/// Minimum level (dB) an [`Envelope`] treats as silence.
+const ENVELOPE_FLOOR_DB: f32 = -60.0;
+
+/// Attack/decay/sustain/release timing (ms) for an [`Envelope`].
+/// Unlike [`EnvelopeSettings`], there is no `sustain_hold_ms` - the sustain
+/// plateau holds open until an explicit `note_off`.
+#[derive(Debug, Clone, Copy)]
+pub struct EnvelopeTiming {
+    pub attack_ms: f32,
+    pub decay_ms: f32,
+    /// Sustain level, as a fraction (0..1) of unity gain.
+    pub sustain: f32,
+    pub release_ms: f32,
+}
+
+impl Default for EnvelopeTiming {
+    fn default() -> Self {
+        Self {
+            attack_ms: 10.0,
+            decay_ms: 100.0,
+            sustain: 0.6,
+            release_ms: 300.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EnvelopeGateStage {
+    Idle,
+    Attack,
+    Decay,
+    Sustain,
+    Release,
+}
+
+/// An attack/decay/sustain/release envelope driven by explicit
+/// `note_on()`/`note_off()`, rather than auto-releasing after a fixed hold
+/// like [`TriggerEnvelope`].
+///
+/// Levels are tracked in dB and converted with `gain = 10^(db/20)`, so each
+/// stage rises and falls along a perceptual curve instead of a linear ramp -
+/// closer to how FM synth envelope generators (e.g. the YM2612) behave.
+/// `note_off` releases from whatever level the envelope is *currently* at,
+/// regardless of which stage it was in.
+#[derive(Debug, Clone, Copy)]
+pub struct Envelope {
+    timing: EnvelopeTiming,
+    stage: EnvelopeGateStage,
+    stage_elapsed_ms: f32,
+    current_db: f32,
+    release_start_db: f32,
+}
+
+impl Envelope {
+    /// Create an idle envelope (silent until `note_on`).
+    pub fn new(timing: EnvelopeTiming) -> Self {
+        Self {
+            timing,
+            stage: EnvelopeGateStage::Idle,
+            stage_elapsed_ms: 0.0,
+            current_db: ENVELOPE_FLOOR_DB,
+            release_start_db: ENVELOPE_FLOOR_DB,
+        }
+    }
+
+    /// Begin (or retrigger) the attack stage.
+    pub fn note_on(&mut self) {
+        self.stage = EnvelopeGateStage::Attack;
+        self.stage_elapsed_ms = 0.0;
+    }
+
+    /// Begin releasing from the envelope's current level.
+    pub fn note_off(&mut self) {
+        self.release_start_db = self.current_db;
+        self.stage = EnvelopeGateStage::Release;
+        self.stage_elapsed_ms = 0.0;
+    }
+
+    /// Advance the envelope by `dt_ms` and return its instantaneous gain.
+    pub fn tick(&mut self, dt_ms: f32) -> f32 {
+        self.stage_elapsed_ms += dt_ms;
+        let sustain_db = gain_to_db(self.timing.sustain);
+
+        match self.stage {
+            EnvelopeGateStage::Idle => {
+                self.current_db = ENVELOPE_FLOOR_DB;
+            }
+            EnvelopeGateStage::Attack => {
+                let t = (self.stage_elapsed_ms / self.timing.attack_ms.max(1.0)).min(1.0);
+                self.current_db = lerp(ENVELOPE_FLOOR_DB, 0.0, t);
+                if t >= 1.0 {
+                    self.stage = EnvelopeGateStage::Decay;
+                    self.stage_elapsed_ms = 0.0;
+                }
+            }
+            EnvelopeGateStage::Decay => {
+                let t = (self.stage_elapsed_ms / self.timing.decay_ms.max(1.0)).min(1.0);
+                self.current_db = lerp(0.0, sustain_db, t);
+                if t >= 1.0 {
+                    self.stage = EnvelopeGateStage::Sustain;
+                    self.stage_elapsed_ms = 0.0;
+                }
+            }
+            EnvelopeGateStage::Sustain => {
+                self.current_db = sustain_db;
+            }
+            EnvelopeGateStage::Release => {
+                let t = (self.stage_elapsed_ms / self.timing.release_ms.max(1.0)).min(1.0);
+                self.current_db = lerp(self.release_start_db, ENVELOPE_FLOOR_DB, t);
+                if t >= 1.0 {
+                    self.stage = EnvelopeGateStage::Idle;
+                    self.current_db = ENVELOPE_FLOOR_DB;
+                }
+            }
+        }
+
+        db_to_gain(self.current_db)
+    }
+
+    /// Current instantaneous gain without advancing the envelope.
+    pub fn value(&self) -> f32 {
+        db_to_gain(self.current_db)
+    }
+
+    /// Whether the envelope has fully released and gone silent.
+    pub fn is_idle(&self) -> bool {
+        self.stage == EnvelopeGateStage::Idle
     }
 }
 
+fn db_to_gain(db: f32) -> f32 {
+    10f32.powf(db / 20.0)
+}
+
+fn gain_to_db(gain: f32) -> f32 {
+    20.0 * gain.max(0.00001).log10()
+}
+
 /// State tracker for values that should decay when no input is received.
 #[derive(Debug, Clone, Copy)]
 pub struct DecayingValue {
@@ -227,4 +816,182 @@ mod tests {
         decay.update(-1.0);
         assert!(decay.value() < 0.8);
     }
+
+    #[test]
+    fn volume_table_maps_unity_level_to_unity_gain() {
+        let table = VolumeTable::new(60.0);
+        assert!((table.amplitude(1.0) - 1.0).abs() < 0.001);
+        assert!(table.amplitude(0.0) < 0.01);
+    }
+
+    #[test]
+    fn trigger_envelope_rises_then_falls_to_zero() {
+        let settings = EnvelopeSettings {
+            attack_ms: 10.0,
+            decay_ms: 10.0,
+            sustain: 0.5,
+            sustain_hold_ms: 10.0,
+            release_ms: 10.0,
+        };
+        let mut envelope = TriggerEnvelope::trigger(settings, 1.0);
+
+        let peak = envelope.advance(10.0);
+        assert!(peak > 0.9);
+
+        for _ in 0..10 {
+            envelope.advance(10.0);
+        }
+        assert!(envelope.is_finished());
+    }
+
+    #[test]
+    fn envelope_rises_through_attack_decay_to_sustain() {
+        let timing = EnvelopeTiming {
+            attack_ms: 10.0,
+            decay_ms: 10.0,
+            sustain: 0.5,
+            release_ms: 10.0,
+        };
+        let mut envelope = Envelope::new(timing);
+        envelope.note_on();
+
+        let peak = envelope.tick(10.0);
+        assert!(peak > 0.9);
+
+        for _ in 0..10 {
+            envelope.tick(10.0);
+        }
+        assert!((envelope.value() - 0.5).abs() < 0.01);
+        assert!(!envelope.is_idle());
+    }
+
+    #[test]
+    fn envelope_note_off_releases_from_current_level_not_peak() {
+        let timing = EnvelopeTiming {
+            attack_ms: 100.0,
+            decay_ms: 100.0,
+            sustain: 0.3,
+            release_ms: 50.0,
+        };
+        let mut envelope = Envelope::new(timing);
+        envelope.note_on();
+
+        // Release mid-attack, well below the eventual sustain level.
+        let mid_attack = envelope.tick(30.0);
+        envelope.note_off();
+        let just_after_release = envelope.tick(1.0);
+
+        assert!(just_after_release <= mid_attack);
+
+        for _ in 0..20 {
+            envelope.tick(10.0);
+        }
+        assert!(envelope.is_idle());
+        assert!(envelope.value() < 0.01);
+    }
+
+    #[test]
+    fn param_smoother_density_envelope_overrides_plain_smoothing() {
+        let mut smoother = ParamSmoother::new();
+        smoother.enable_density_envelope(EnvelopeTiming {
+            attack_ms: 10.0,
+            decay_ms: 10.0,
+            sustain: 0.8,
+            release_ms: 10.0,
+        });
+
+        smoother.gate_envelopes(true);
+        for _ in 0..5 {
+            smoother.update(10.0);
+        }
+        assert!(smoother.density.value() > 0.0);
+
+        smoother.gate_envelopes(false);
+        for _ in 0..10 {
+            smoother.update(10.0);
+        }
+        assert!(smoother.density.value() < 0.1);
+    }
+
+    #[test]
+    fn with_time_settles_within_seven_time_constants() {
+        // A 1 second time constant sampled at 100 Hz closes 1 - 1/e of the
+        // gap per 100 steps, so it is within SETTLE_EPSILON after about
+        // 6.9 time constants (~691 steps).
+        let mut param = SmoothedParam::with_time(0.0, 1000.0, 1000.0, 100.0);
+        param.set_target(1.0);
+
+        for _ in 0..700 {
+            param.update();
+        }
+
+        assert!(param.is_settled());
+        assert!(param.steps_left() < 300);
+    }
+
+    #[test]
+    fn linear_style_reaches_target_exactly_without_overshoot() {
+        let mut param = SmoothedParam::with_coefficients(0.0, 0.1, 0.1);
+        param.set_style(SmoothingStyle::Linear);
+        param.set_target(1.0);
+
+        for _ in 0..9 {
+            param.update();
+            assert!(param.value() <= 1.0);
+        }
+        param.update();
+
+        assert_eq!(param.value(), 1.0);
+        assert!(param.is_settled());
+    }
+
+    #[test]
+    fn next_block_fills_successive_smoothed_values() {
+        let mut param = SmoothedParam::new(0.0);
+        param.set_target(1.0);
+
+        let mut block = [0.0; 8];
+        param.next_block(&mut block);
+
+        assert!(block.windows(2).all(|pair| pair[1] >= pair[0]));
+        assert_eq!(block[block.len() - 1], param.value());
+    }
+
+    #[test]
+    fn steps_left_counts_down_to_zero_as_the_param_settles() {
+        let mut param = SmoothedParam::new(0.0);
+        param.set_target(1.0);
+
+        let initial_steps = param.steps_left();
+        assert!(initial_steps > 0);
+
+        for _ in 0..200 {
+            param.update();
+        }
+
+        assert_eq!(param.steps_left(), 0);
+    }
+
+    #[test]
+    fn envelope_pool_sums_concurrent_envelopes_and_prunes_finished() {
+        let settings = EnvelopeSettings {
+            attack_ms: 1.0,
+            decay_ms: 1.0,
+            sustain: 1.0,
+            sustain_hold_ms: 1.0,
+            release_ms: 1.0,
+        };
+        let mut pool = EnvelopePool::new();
+        pool.spawn(settings, 0.5);
+        pool.spawn(settings, 0.5);
+
+        let sum = pool.advance(0.5);
+        assert!(sum > 0.0);
+        assert_eq!(pool.active_count(), 2);
+
+        for _ in 0..10 {
+            pool.advance(10.0);
+        }
+        assert_eq!(pool.active_count(), 0);
+    }
 }
@@ -0,0 +1,165 @@
+//! Serializable engine snapshots.
+//!
+//! Captures everything `Engine` needs to resume a session exactly where it
+//! left off - harmony state, preset, smoother targets and coefficients,
+//! mixer balance, and click energy - as a plain serde-friendly document so
+//! a host can save and restore it, or register it as a named preset.
+
+use serde::{Deserialize, Serialize};
+
+use crate::harmony::{ChordDegree, Preset};
+use crate::mixer::Mixer;
+use crate::smoothing::SmoothingSnapshot;
+use crate::types::{HarmonyState, MusicParams};
+
+/// Bumped whenever the snapshot shape changes, so hosts can detect and
+/// migrate documents saved by an older version of the engine.
+pub const SNAPSHOT_VERSION: u32 = 3;
+
+/// A point-in-time capture of `Engine`'s mutable musical state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngineSnapshot {
+    pub version: u32,
+    /// RNG seed the engine was created with. Restoring replays determinism
+    /// from this seed; the exact RNG draw position is not preserved.
+    pub seed: u64,
+    pub preset: Preset,
+    pub harmony: HarmonyState,
+    pub chord_pool: Option<Vec<ChordDegree>>,
+    pub modulation_rate_override: Option<f32>,
+    /// Time since the last modulation (ms). The stochastic modulation model
+    /// has no pre-committed "pending" target; this is the state that
+    /// actually governs when the next modulation roll is allowed.
+    pub time_since_modulation: u64,
+    /// Last chord degree played, for hosts that want to resume mid-phrase.
+    pub current_chord: u8,
+    pub params: MusicParams,
+    pub smoothing: SmoothingSnapshot,
+    pub mixer: Mixer,
+    pub click_energy: f32,
+}
+
+/// A named, savable `EngineSnapshot`, for hosts that let a user author and
+/// pick between their own custom soundscapes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomPreset {
+    pub name: String,
+    pub snapshot: EngineSnapshot,
+}
+
+/// A small registry of user-authored presets, keyed by name.
+///
+/// Backed by a `Vec` rather than a map - the registry is expected to hold a
+/// handful of entries at most, and this keeps it consistent with the rest
+/// of the crate's preference for linear lookup over hashing (see
+/// `HarmonyManager::chord_pool`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PresetLibrary {
+    presets: Vec<CustomPreset>,
+}
+
+impl PresetLibrary {
+    /// Create an empty library.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a snapshot under `name`, replacing any existing entry with
+    /// the same name.
+    pub fn register(&mut self, name: impl Into<String>, snapshot: EngineSnapshot) {
+        let name = name.into();
+        self.remove(&name);
+        self.presets.push(CustomPreset { name, snapshot });
+    }
+
+    /// Look up a preset by name.
+    pub fn get(&self, name: &str) -> Option<&EngineSnapshot> {
+        self.presets
+            .iter()
+            .find(|p| p.name == name)
+            .map(|p| &p.snapshot)
+    }
+
+    /// Remove a preset by name. Returns `true` if one was removed.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.presets.len();
+        self.presets.retain(|p| p.name != name);
+        self.presets.len() != before
+    }
+
+    /// List the names of every registered preset, in registration order.
+    pub fn names(&self) -> Vec<&str> {
+        self.presets.iter().map(|p| p.name.as_str()).collect()
+    }
+
+    /// Number of registered presets.
+    pub fn len(&self) -> usize {
+        self.presets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.presets.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::harmony::HarmonyManager;
+    use crate::smoothing::ParamSmoother;
+    use crate::types::{HarmonyState, Mode};
+
+    fn sample_snapshot() -> EngineSnapshot {
+        let harmony = HarmonyManager::new(1, Preset::Ambient);
+        EngineSnapshot {
+            version: SNAPSHOT_VERSION,
+            seed: 1,
+            preset: Preset::Ambient,
+            harmony: HarmonyState {
+                root: 2,
+                mode: Mode::Dorian,
+                tension: 0.4,
+            },
+            chord_pool: None,
+            modulation_rate_override: None,
+            time_since_modulation: harmony.time_since_modulation(),
+            current_chord: 0,
+            params: MusicParams::default(),
+            smoothing: ParamSmoother::new().coefficients(),
+            mixer: Mixer::new(),
+            click_energy: 0.0,
+        }
+    }
+
+    #[test]
+    fn library_round_trips_a_registered_preset() {
+        let mut library = PresetLibrary::new();
+        library.register("my soundscape", sample_snapshot());
+
+        let found = library.get("my soundscape").expect("preset should exist");
+        assert_eq!(found.harmony.root, 2);
+        assert_eq!(library.names(), vec!["my soundscape"]);
+    }
+
+    #[test]
+    fn registering_same_name_twice_replaces_the_entry() {
+        let mut library = PresetLibrary::new();
+        library.register("a", sample_snapshot());
+        let mut second = sample_snapshot();
+        second.harmony.root = 7;
+        library.register("a", second);
+
+        assert_eq!(library.len(), 1);
+        assert_eq!(library.get("a").unwrap().harmony.root, 7);
+    }
+
+    #[test]
+    fn remove_reports_whether_a_preset_existed() {
+        let mut library = PresetLibrary::new();
+        library.register("a", sample_snapshot());
+
+        assert!(library.remove("a"));
+        assert!(!library.remove("a"));
+        assert!(library.is_empty());
+    }
+}
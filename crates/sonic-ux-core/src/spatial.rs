@@ -0,0 +1,177 @@
+//! Spatialization: treats the pointer as a moving emitter in a normalized
+//! 2D field in front of a fixed "listener", deriving a stereo pan from
+//! horizontal position and a distance-based attenuation/reverb send from
+//! vertical position and speed.
+
+use crate::types::MusicEvent;
+
+/// Per-preset distance rolloff curve.
+#[derive(Debug, Clone, Copy)]
+pub struct RolloffSettings {
+    /// Exponent applied to normalized distance before attenuation; values
+    /// above 1.0 keep gain near-unity close to the listener and fall off
+    /// sharply only near the far edge of the field.
+    pub curve: f32,
+    /// Gain retained even at the far edge of the field (0..1), so distant
+    /// events fade but never vanish entirely.
+    pub floor_gain: f32,
+    /// Reverb send contributed at the far edge of the field (0..1).
+    pub max_reverb_send: f32,
+}
+
+impl Default for RolloffSettings {
+    fn default() -> Self {
+        Self {
+            curve: 2.0,
+            floor_gain: 0.3,
+            max_reverb_send: 0.5,
+        }
+    }
+}
+
+/// Computes pan/distance/reverb from pointer position and places discrete
+/// events in the resulting stereo field.
+#[derive(Debug, Clone, Copy)]
+pub struct Spatializer {
+    rolloff: RolloffSettings,
+}
+
+impl Spatializer {
+    /// Create a spatializer with the given rolloff curve.
+    pub fn new(rolloff: RolloffSettings) -> Self {
+        Self { rolloff }
+    }
+
+    /// Replace the rolloff curve (e.g. after a preset change).
+    pub fn set_rolloff(&mut self, rolloff: RolloffSettings) {
+        self.rolloff = rolloff;
+    }
+
+    /// Stereo pan (-1..1) for a normalized horizontal position (0..1).
+    pub fn pan(&self, x: f32) -> f32 {
+        ((x.clamp(0.0, 1.0) - 0.5) * 2.0).clamp(-1.0, 1.0)
+    }
+
+    /// Normalized distance (0..1) from the listener, seated at the near
+    /// edge of the field. A larger `y` and faster movement both
+    /// read as "further away".
+    fn distance(&self, y: f32, speed: f32) -> f32 {
+        (y.clamp(0.0, 1.0) * 0.7 + speed.clamp(0.0, 1.0) * 0.3).clamp(0.0, 1.0)
+    }
+
+    /// Distance attenuation gain (0..1) for a given position/speed.
+    pub fn attenuation(&self, y: f32, speed: f32) -> f32 {
+        let falloff = 1.0 - self.distance(y, speed).powf(self.rolloff.curve);
+        falloff.max(self.rolloff.floor_gain)
+    }
+
+    /// Distance-driven reverb send (0..1) for a given position/speed.
+    pub fn reverb_send(&self, y: f32, speed: f32) -> f32 {
+        self.distance(y, speed) * self.rolloff.max_reverb_send
+    }
+
+    /// Place a discrete event in the field: set its pan from `x` and scale
+    /// its velocity/level by the distance attenuation for `y`/`speed`.
+    /// Only `Pluck`, `PadChord` and `Accent` are placed; every other event
+    /// (including `PadVoiceOn`, which carries its own pan) passes through
+    /// unchanged.
+    pub fn place(&self, event: MusicEvent, x: f32, y: f32, speed: f32) -> MusicEvent {
+        let pan = self.pan(x);
+        let gain = self.attenuation(y, speed);
+
+        match event {
+            MusicEvent::Pluck {
+                note,
+                velocity,
+                salience,
+                ..
+            } => MusicEvent::Pluck {
+                note,
+                velocity: (velocity * gain).clamp(0.0, 1.0),
+                salience,
+                pan,
+            },
+            MusicEvent::PadChord {
+                notes,
+                velocity,
+                salience,
+                ..
+            } => MusicEvent::PadChord {
+                notes,
+                velocity: (velocity * gain).clamp(0.0, 1.0),
+                salience,
+                pan,
+            },
+            MusicEvent::Accent {
+                strength,
+                salience,
+                ..
+            } => MusicEvent::Accent {
+                strength: (strength * gain).clamp(0.0, 1.0),
+                salience,
+                pan,
+            },
+            other => other,
+        }
+    }
+}
+
+impl Default for Spatializer {
+    fn default() -> Self {
+        Self::new(RolloffSettings::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pan_maps_edges_to_hard_left_and_right() {
+        let spatializer = Spatializer::default();
+        assert!((spatializer.pan(0.0) - (-1.0)).abs() < 0.001);
+        assert!((spatializer.pan(1.0) - 1.0).abs() < 0.001);
+        assert!(spatializer.pan(0.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn distant_events_are_quieter_and_wetter_than_near_ones() {
+        let spatializer = Spatializer::default();
+        let near = MusicEvent::Pluck {
+            note: 60,
+            velocity: 1.0,
+            salience: 1.0,
+            pan: 0.0,
+        };
+        let far = near.clone();
+
+        let placed_near = spatializer.place(near, 0.5, 0.0, 0.0);
+        let placed_far = spatializer.place(far, 0.5, 1.0, 1.0);
+
+        let near_velocity = match placed_near {
+            MusicEvent::Pluck { velocity, .. } => velocity,
+            _ => panic!("expected pluck"),
+        };
+        let far_velocity = match placed_far {
+            MusicEvent::Pluck { velocity, .. } => velocity,
+            _ => panic!("expected pluck"),
+        };
+
+        assert!(far_velocity < near_velocity);
+        assert!(spatializer.reverb_send(1.0, 1.0) > spatializer.reverb_send(0.0, 0.0));
+    }
+
+    #[test]
+    fn non_panned_events_pass_through_unchanged() {
+        let spatializer = Spatializer::default();
+        let mute = MusicEvent::Mute {
+            on: true,
+            salience: 1.0,
+        };
+
+        assert!(matches!(
+            spatializer.place(mute, 0.9, 0.9, 0.9),
+            MusicEvent::Mute { on: true, .. }
+        ));
+    }
+}
@@ -0,0 +1,393 @@
+//! Sample-accurate audio renderer (optional `synth` feature).
+//!
+//! Renders an [`OutputFrame`] into a PCM buffer so SonicUX can produce sound
+//! directly without an external host. A polyphonic [`VoiceManager`] allocates
+//! a voice per note-on and frees it on note-off (or steals the oldest voice
+//! once the cap passed to [`VoiceManager::new`] is reached); each voice is a simple
+//! oscillator through a per-voice ADSR envelope. Control-rate params are
+//! tweened between engine frames so audio-rate knob motion doesn't zipper.
+
+use std::f32::consts::TAU;
+
+use crate::types::{HoldState, MusicEvent, MusicParams, OutputFrame};
+
+/// Oscillator waveform a voice renders.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Waveform {
+    Sine,
+    Saw,
+}
+
+/// Attack/decay/sustain/release timing for every voice, in seconds.
+#[derive(Debug, Clone, Copy)]
+pub struct AdsrSettings {
+    pub attack_s: f32,
+    pub decay_s: f32,
+    /// Sustain level (0..1) held until note-off.
+    pub sustain: f32,
+    pub release_s: f32,
+}
+
+impl Default for AdsrSettings {
+    fn default() -> Self {
+        Self {
+            attack_s: 0.01,
+            decay_s: 0.15,
+            sustain: 0.6,
+            release_s: 0.3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EnvStage {
+    Attack,
+    Decay,
+    Sustain,
+    Release,
+    Idle,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Voice {
+    note: u8,
+    freq: f32,
+    phase: f32,
+    waveform: Waveform,
+    stage: EnvStage,
+    level: f32,
+    /// Note velocity (0..1), held for the life of the voice.
+    gain: f32,
+    /// Samples since this voice was allocated, for oldest-voice stealing.
+    age: u64,
+}
+
+impl Voice {
+    fn silent() -> Self {
+        Self {
+            note: 0,
+            freq: 0.0,
+            phase: 0.0,
+            waveform: Waveform::Sine,
+            stage: EnvStage::Idle,
+            level: 0.0,
+            gain: 0.0,
+            age: 0,
+        }
+    }
+
+    fn is_free(&self) -> bool {
+        self.stage == EnvStage::Idle
+    }
+
+    fn note_on(&mut self, note: u8, velocity: f32, waveform: Waveform) {
+        self.note = note;
+        self.freq = midi_note_to_freq(note);
+        self.waveform = waveform;
+        self.stage = EnvStage::Attack;
+        self.level = 0.0;
+        self.gain = velocity.clamp(0.0, 1.0);
+        self.age = 0;
+    }
+
+    fn note_off(&mut self) {
+        if self.stage != EnvStage::Idle {
+            self.stage = EnvStage::Release;
+        }
+    }
+
+    fn advance_env(&mut self, adsr: &AdsrSettings, sample_dt: f32) {
+        match self.stage {
+            EnvStage::Attack => {
+                self.level += sample_dt / adsr.attack_s.max(0.001);
+                if self.level >= 1.0 {
+                    self.level = 1.0;
+                    self.stage = EnvStage::Decay;
+                }
+            }
+            EnvStage::Decay => {
+                let fall = (1.0 - adsr.sustain).max(0.0001);
+                self.level -= sample_dt / adsr.decay_s.max(0.001) * fall;
+                if self.level <= adsr.sustain {
+                    self.level = adsr.sustain;
+                    self.stage = EnvStage::Sustain;
+                }
+            }
+            EnvStage::Sustain => {}
+            EnvStage::Release => {
+                self.level -= sample_dt / adsr.release_s.max(0.001);
+                if self.level <= 0.0 {
+                    self.level = 0.0;
+                    self.stage = EnvStage::Idle;
+                }
+            }
+            EnvStage::Idle => {}
+        }
+    }
+
+    fn sample(&mut self, adsr: &AdsrSettings, sample_rate: f32, brightness: f32) -> f32 {
+        self.advance_env(adsr, 1.0 / sample_rate);
+        if self.stage == EnvStage::Idle {
+            return 0.0;
+        }
+
+        // Brightness crossfades sine (dark) into saw (bright) rather than
+        // hard-switching waveform.
+        let sine = (self.phase * TAU).sin();
+        let saw = 2.0 * (self.phase - (self.phase + 0.5).floor());
+        let mix = match self.waveform {
+            Waveform::Sine => sine,
+            Waveform::Saw => sine + (saw - sine) * brightness.clamp(0.0, 1.0),
+        };
+
+        self.phase += self.freq / sample_rate;
+        if self.phase >= 1.0 {
+            self.phase -= 1.0;
+        }
+        self.age += 1;
+
+        mix * self.level * self.gain
+    }
+}
+
+fn midi_note_to_freq(note: u8) -> f32 {
+    440.0 * 2f32.powf((note as f32 - 69.0) / 12.0)
+}
+
+/// Polyphonic voice allocator with a fixed cap and oldest-voice stealing.
+#[derive(Debug)]
+pub struct VoiceManager {
+    voices: Vec<Voice>,
+    adsr: AdsrSettings,
+}
+
+impl VoiceManager {
+    /// Create a voice manager with room for `max_voices` simultaneous notes.
+    pub fn new(max_voices: usize) -> Self {
+        Self {
+            voices: vec![Voice::silent(); max_voices.max(1)],
+            adsr: AdsrSettings::default(),
+        }
+    }
+
+    /// Set the ADSR envelope shared by all voices.
+    pub fn set_adsr(&mut self, adsr: AdsrSettings) {
+        self.adsr = adsr;
+    }
+
+    fn allocate(&mut self, note: u8, velocity: f32, waveform: Waveform) {
+        if let Some(voice) = self.voices.iter_mut().find(|v| v.is_free()) {
+            voice.note_on(note, velocity, waveform);
+            return;
+        }
+        // No free voice: steal the oldest one.
+        let oldest = self
+            .voices
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, v)| v.age)
+            .map(|(i, _)| i)
+            .unwrap_or(0);
+        self.voices[oldest].note_on(note, velocity, waveform);
+    }
+
+    fn release(&mut self, note: u8) {
+        for voice in &mut self.voices {
+            if voice.note == note && !voice.is_free() {
+                voice.note_off();
+            }
+        }
+    }
+
+    fn release_all(&mut self) {
+        for voice in &mut self.voices {
+            voice.note_off();
+        }
+    }
+
+    fn sum_sample(&mut self, sample_rate: f32, brightness: f32) -> f32 {
+        self.voices
+            .iter_mut()
+            .map(|v| v.sample(&self.adsr, sample_rate, brightness))
+            .sum()
+    }
+}
+
+/// Linearly-tweened control-rate parameter, ramped over a frame's `dt_ms`
+/// instead of jumping, to avoid zipper noise at the audio rate.
+#[derive(Debug, Clone, Copy, Default)]
+struct Tween {
+    current: f32,
+    target: f32,
+    step: f32,
+}
+
+impl Tween {
+    fn set_target(&mut self, target: f32, dt_ms: u64, sample_rate: f32) {
+        self.target = target;
+        let samples = ((dt_ms as f32 / 1000.0) * sample_rate).max(1.0);
+        self.step = (self.target - self.current) / samples;
+    }
+
+    fn advance(&mut self) -> f32 {
+        if (self.current - self.target).abs() > self.step.abs().max(1e-6) {
+            self.current += self.step;
+        } else {
+            self.current = self.target;
+        }
+        self.current
+    }
+}
+
+/// Renders successive `OutputFrame`s into an interleaved stereo PCM buffer.
+#[derive(Debug)]
+pub struct SynthRenderer {
+    voices: VoiceManager,
+    hold_note: Option<u8>,
+    warmth: Tween,
+    brightness: Tween,
+    reverb: Tween,
+    master: Tween,
+    width: Tween,
+}
+
+impl SynthRenderer {
+    /// Create a renderer with a fixed polyphony cap.
+    pub fn new(max_voices: usize) -> Self {
+        Self {
+            voices: VoiceManager::new(max_voices),
+            hold_note: None,
+            warmth: Tween::default(),
+            brightness: Tween::default(),
+            reverb: Tween::default(),
+            master: Tween::default(),
+            width: Tween::default(),
+        }
+    }
+
+    /// Ingest one engine frame: trigger/release voices and set new ramp
+    /// targets for control-rate params. Call once per engine update, then
+    /// pull audio with [`SynthRenderer::render`] at the output sample rate.
+    pub fn ingest(&mut self, frame: &OutputFrame, dt_ms: u64, sample_rate: u32) {
+        for event in &frame.events {
+            self.ingest_event(event);
+        }
+        self.ingest_hold(frame.hold);
+        self.ingest_params(&frame.params, dt_ms, sample_rate);
+    }
+
+    fn ingest_event(&mut self, event: &MusicEvent) {
+        match event {
+            MusicEvent::Pluck { note, velocity, .. } => {
+                self.voices.allocate(*note, *velocity, Waveform::Saw);
+            }
+            MusicEvent::PadChord { notes, velocity, .. } => {
+                for &note in notes {
+                    self.voices.allocate(note, *velocity, Waveform::Sine);
+                }
+            }
+            MusicEvent::PadVoiceOn { note, level, .. } => {
+                self.voices.allocate(*note, *level, Waveform::Sine);
+            }
+            MusicEvent::PadVoiceOff { note } => self.voices.release(*note),
+            MusicEvent::Mute { on, .. } => {
+                if *on {
+                    self.voices.release_all();
+                }
+            }
+            MusicEvent::Cadence { .. } | MusicEvent::Accent { .. } => {}
+        }
+    }
+
+    fn ingest_hold(&mut self, hold: Option<HoldState>) {
+        match (self.hold_note, hold) {
+            (Some(old), Some(new)) if old != new.note => {
+                self.voices.release(old);
+                self.voices.allocate(new.note, new.velocity, Waveform::Sine);
+                self.hold_note = Some(new.note);
+            }
+            (None, Some(new)) => {
+                self.voices.allocate(new.note, new.velocity, Waveform::Sine);
+                self.hold_note = Some(new.note);
+            }
+            (Some(old), None) => {
+                self.voices.release(old);
+                self.hold_note = None;
+            }
+            _ => {}
+        }
+    }
+
+    fn ingest_params(&mut self, params: &MusicParams, dt_ms: u64, sample_rate: u32) {
+        let sr = sample_rate as f32;
+        self.warmth.set_target(params.warmth, dt_ms, sr);
+        self.brightness.set_target(params.brightness, dt_ms, sr);
+        self.reverb.set_target(params.reverb, dt_ms, sr);
+        self.master.set_target(params.master, dt_ms, sr);
+        self.width.set_target(params.width, dt_ms, sr);
+    }
+
+    /// Fill `out` (interleaved stereo, `out.len()` must be even) with the
+    /// next block of audio at `sample_rate`.
+    pub fn render(&mut self, out: &mut [f32], sample_rate: u32) {
+        let sr = sample_rate as f32;
+        for frame in out.chunks_exact_mut(2) {
+            let brightness = self.brightness.advance();
+            let master = self.master.advance();
+            let width = self.width.advance();
+            // Warmth/reverb are tweened alongside the other params but are
+            // not applied to the output: this renderer has no filter or
+            // reverb stage.
+            let _ = self.warmth.advance();
+            let _ = self.reverb.advance();
+
+            let mono = self.voices.sum_sample(sr, brightness) * master;
+            let spread = width.clamp(0.0, 1.0);
+            frame[0] = mono * (1.0 - spread * 0.5);
+            frame[1] = mono * (1.0 - (1.0 - spread) * 0.5);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn voice_manager_steals_oldest_voice_when_full() {
+        let mut vm = VoiceManager::new(1);
+        vm.allocate(60, 1.0, Waveform::Sine);
+        vm.allocate(64, 1.0, Waveform::Sine);
+        assert_eq!(vm.voices[0].note, 64);
+    }
+
+    #[test]
+    fn voice_envelope_reaches_zero_after_release() {
+        let adsr = AdsrSettings {
+            attack_s: 0.001,
+            decay_s: 0.001,
+            sustain: 0.5,
+            release_s: 0.001,
+        };
+        let mut voice = Voice::silent();
+        voice.note_on(60, 1.0, Waveform::Sine);
+        for _ in 0..200 {
+            voice.advance_env(&adsr, 1.0 / 44100.0);
+        }
+        voice.note_off();
+        for _ in 0..200 {
+            voice.advance_env(&adsr, 1.0 / 44100.0);
+        }
+        assert_eq!(voice.level, 0.0);
+        assert!(voice.is_free());
+    }
+
+    #[test]
+    fn render_fills_every_sample() {
+        let mut renderer = SynthRenderer::new(4);
+        let mut out = vec![f32::NAN; 256];
+        renderer.render(&mut out, 44100);
+        assert_eq!(out.len(), 256);
+        assert!(out.iter().all(|s| s.is_finite()));
+    }
+}
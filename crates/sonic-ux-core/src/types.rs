@@ -3,6 +3,8 @@
 //! Defines input contracts (InteractionFrame, InteractionEvent) and
 //! output contracts (OutputFrame, MusicParams, MusicEvent).
 
+use serde::{Deserialize, Serialize};
+
 /// Continuous input sent at a fixed cadence from the host.
 ///
 /// All position values are normalized to 0..1 range.
@@ -81,14 +83,28 @@ pub struct OutputFrame {
     pub harmony: HarmonyState,
     /// Discrete musical events triggered this frame
     pub events: Vec<MusicEvent>,
+    /// Click-and-hold voice, present while the pointer is held down
+    pub hold: Option<HoldState>,
+    /// Summed instantaneous value (0..1) of all per-event ADSR envelopes
+    /// currently running, before the perceptual loudness curve is applied
+    pub envelope_level: f32,
     /// Optional diagnostic output for debugging/visualization
     pub diagnostics: Option<DiagnosticOutput>,
 }
 
+/// Sustained note voiced while the pointer is held down.
+#[derive(Debug, Clone, Copy)]
+pub struct HoldState {
+    /// Note currently sounding (MIDI-style, 0 = C in octave 0)
+    pub note: u8,
+    /// Voice velocity/loudness (0..1)
+    pub velocity: f32,
+}
+
 /// Continuous musical parameters (all 0..1 unless noted).
 ///
 /// These are smoothed, bounded values suitable for direct mapping to audio parameters.
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
 pub struct MusicParams {
     /// Overall intensity / master level
     pub master: f32,
@@ -106,10 +122,12 @@ pub struct MusicParams {
     pub density: f32,
     /// Harmonic complexity / tension level
     pub tension: f32,
+    /// Stereo placement of the pointer emitter (-1..1), 0 = center
+    pub pan: f32,
 }
 
 /// Current harmonic state.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct HarmonyState {
     /// Root note (0-11, where 0 = C)
     pub root: u8,
@@ -130,7 +148,7 @@ impl Default for HarmonyState {
 }
 
 /// Musical modes/scales supported by the engine.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum Mode {
     #[default]
     Major,
@@ -185,12 +203,16 @@ pub enum MusicEvent {
         note: u8,
         velocity: f32,
         salience: f32,
+        /// Stereo placement (-1..1), 0 = center
+        pan: f32,
     },
     /// Sustained harmonic bed (section change, idle)
     PadChord {
         notes: Vec<u8>,
         velocity: f32,
         salience: f32,
+        /// Stereo placement (-1..1), 0 = center
+        pan: f32,
     },
     /// Key/mode transition marker
     Cadence {
@@ -202,16 +224,36 @@ pub enum MusicEvent {
     Accent {
         strength: f32,
         salience: f32,
+        /// Stereo placement (-1..1), 0 = center
+        pan: f32,
     },
     /// Fade out / silence trigger
     Mute {
         on: bool,
         salience: f32,
     },
+    /// One voice of a generative drone/pad bed swells in
+    PadVoiceOn {
+        note: u8,
+        /// Detune multiplier around 1.0 (e.g. 0.99, 1.0, 1.01)
+        detune: f32,
+        /// Voice level (0..1)
+        level: f32,
+        /// Stereo placement (-1..1), 0 = center
+        pan: f32,
+    },
+    /// A drone/pad voice releases
+    PadVoiceOff {
+        note: u8,
+    },
 }
 
 impl MusicEvent {
     /// Get the salience value for this event.
+    ///
+    /// Drone voice events carry no salience of their own (they're a
+    /// continuous background bed rather than a foregrounded gesture), so
+    /// they report 0.
     pub fn salience(&self) -> f32 {
         match self {
             MusicEvent::Pluck { salience, .. } => *salience,
@@ -219,6 +261,7 @@ impl MusicEvent {
             MusicEvent::Cadence { salience, .. } => *salience,
             MusicEvent::Accent { salience, .. } => *salience,
             MusicEvent::Mute { salience, .. } => *salience,
+            MusicEvent::PadVoiceOn { .. } | MusicEvent::PadVoiceOff { .. } => 0.0,
         }
     }
 }
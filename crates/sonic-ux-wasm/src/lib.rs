@@ -6,9 +6,9 @@ use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
 use sonic_ux_core::{
-    harmony::ChordDegree, Engine as CoreEngine, InteractionEvent as CoreInteractionEvent,
-    InteractionFrame as CoreInteractionFrame, Mode, MusicEvent as CoreMusicEvent,
-    OutputFrame as CoreOutputFrame, Preset,
+    harmony::ChordDegree, midi::MidiRenderer, Engine as CoreEngine, EngineSnapshot,
+    InteractionEvent as CoreInteractionEvent, InteractionFrame as CoreInteractionFrame, Mode,
+    MusicEvent as CoreMusicEvent, OutputFrame as CoreOutputFrame, Preset, PresetLibrary,
 };
 
 /// WASM-compatible interaction frame.
@@ -120,6 +120,9 @@ pub struct JsOutputFrame {
     pub events: Vec<JsMusicEvent>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hold: Option<JsHoldState>,
+    /// Summed instantaneous value (0..1) of all per-event ADSR envelopes
+    /// currently running, before the perceptual loudness curve is applied.
+    pub envelope_level: f32,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub diagnostics: Option<JsDiagnostics>,
 }
@@ -144,6 +147,8 @@ pub struct JsMusicParams {
     pub density: f32,
     /// Harmonic complexity / tension level (0..1)
     pub tension: f32,
+    /// Stereo placement of the pointer emitter (-1..1), 0 = center
+    pub pan: f32,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -182,12 +187,14 @@ pub enum JsMusicEvent {
         note: u8,
         vel: f32,
         salience: f32,
+        pan: f32,
     },
     #[serde(rename_all = "camelCase")]
     PadChord {
         notes: Vec<u8>,
         vel: f32,
         salience: f32,
+        pan: f32,
     },
     #[serde(rename_all = "camelCase")]
     Cadence {
@@ -196,9 +203,26 @@ pub enum JsMusicEvent {
         salience: f32,
     },
     #[serde(rename_all = "camelCase")]
-    Accent { strength: f32, salience: f32 },
+    Accent {
+        strength: f32,
+        salience: f32,
+        pan: f32,
+    },
     #[serde(rename_all = "camelCase")]
     Mute { on: bool, salience: f32 },
+    #[serde(rename_all = "camelCase")]
+    PadVoiceOn {
+        note: u8,
+        detune: f32,
+        level: f32,
+        pan: f32,
+    },
+    #[serde(rename_all = "camelCase")]
+    PadVoiceOff { note: u8 },
+}
+
+fn flatten_midi(messages: Vec<sonic_ux_core::midi::MidiMessage>) -> Vec<u8> {
+    messages.iter().flat_map(|m| m.as_bytes()).collect()
 }
 
 fn mode_to_string(mode: Mode) -> String {
@@ -225,6 +249,7 @@ fn convert_output(output: CoreOutputFrame) -> JsOutputFrame {
             reverb: output.params.reverb,
             density: output.params.density,
             tension: output.params.tension,
+            pan: output.params.pan,
         },
         harmony: JsHarmonyState {
             root: output.harmony.root,
@@ -236,6 +261,7 @@ fn convert_output(output: CoreOutputFrame) -> JsOutputFrame {
             note: hold.note,
             vel: hold.velocity,
         }),
+        envelope_level: output.envelope_level,
         diagnostics: output.diagnostics.map(|d| JsDiagnostics {
             key: d.key,
             mode: d.mode,
@@ -254,19 +280,23 @@ fn convert_event(event: CoreMusicEvent) -> JsMusicEvent {
             note,
             velocity,
             salience,
+            pan,
         } => JsMusicEvent::Pluck {
             note,
             vel: velocity,
             salience,
+            pan,
         },
         CoreMusicEvent::PadChord {
             notes,
             velocity,
             salience,
+            pan,
         } => JsMusicEvent::PadChord {
             notes,
             vel: velocity,
             salience,
+            pan,
         },
         CoreMusicEvent::Cadence {
             to_root,
@@ -277,10 +307,28 @@ fn convert_event(event: CoreMusicEvent) -> JsMusicEvent {
             mode: mode_to_string(to_mode),
             salience,
         },
-        CoreMusicEvent::Accent { strength, salience } => {
-            JsMusicEvent::Accent { strength, salience }
-        }
+        CoreMusicEvent::Accent {
+            strength,
+            salience,
+            pan,
+        } => JsMusicEvent::Accent {
+            strength,
+            salience,
+            pan,
+        },
         CoreMusicEvent::Mute { on, salience } => JsMusicEvent::Mute { on, salience },
+        CoreMusicEvent::PadVoiceOn {
+            note,
+            detune,
+            level,
+            pan,
+        } => JsMusicEvent::PadVoiceOn {
+            note,
+            detune,
+            level,
+            pan,
+        },
+        CoreMusicEvent::PadVoiceOff { note } => JsMusicEvent::PadVoiceOff { note },
     }
 }
 
@@ -302,6 +350,9 @@ fn parse_chord_degree(s: &str) -> Option<ChordDegree> {
 #[wasm_bindgen]
 pub struct SonicEngine {
     inner: CoreEngine,
+    midi: MidiRenderer,
+    last_midi: Vec<u8>,
+    presets: PresetLibrary,
 }
 
 #[wasm_bindgen]
@@ -317,6 +368,9 @@ impl SonicEngine {
 
         Self {
             inner: CoreEngine::new(seed, preset),
+            midi: MidiRenderer::new(0),
+            last_midi: Vec::new(),
+            presets: PresetLibrary::new(),
         }
     }
 
@@ -328,6 +382,7 @@ impl SonicEngine {
     pub fn update(&mut self, frame: JsValue) -> Result<JsValue, JsError> {
         let frame: InteractionFrame = serde_wasm_bindgen::from_value(frame)?;
         let output = self.inner.update(frame.into());
+        self.last_midi = flatten_midi(self.midi.render(&output));
         let js_output = convert_output(output);
         Ok(serde_wasm_bindgen::to_value(&js_output)?)
     }
@@ -339,15 +394,32 @@ impl SonicEngine {
     #[wasm_bindgen]
     pub fn event(&mut self, event: JsValue) -> Result<JsValue, JsError> {
         let event: InteractionEvent = serde_wasm_bindgen::from_value(event)?;
-        let events: Vec<JsMusicEvent> = self
-            .inner
-            .event(event.into())
-            .into_iter()
-            .map(convert_event)
-            .collect();
+        let core_events = self.inner.event(event.into());
+        self.last_midi = flatten_midi(self.midi.render_events(&core_events));
+        let events: Vec<JsMusicEvent> = core_events.into_iter().map(convert_event).collect();
         Ok(serde_wasm_bindgen::to_value(&events)?)
     }
 
+    /// Packed `[status, data1, data2]` MIDI bytes produced by the most
+    /// recent `update()` or `event()` call.
+    #[wasm_bindgen]
+    pub fn midi_bytes(&self) -> Vec<u8> {
+        self.last_midi.clone()
+    }
+
+    /// Set the MIDI channel (0-15) messages are emitted on.
+    #[wasm_bindgen]
+    pub fn set_midi_channel(&mut self, channel: u8) {
+        self.midi.set_channel(channel);
+    }
+
+    /// Enable or disable emitting a Program Change, chosen from the target
+    /// mode, whenever the harmony modulates (on every `Cadence`).
+    #[wasm_bindgen]
+    pub fn set_midi_program_change_enabled(&mut self, enabled: bool) {
+        self.midi.set_program_change_enabled(enabled);
+    }
+
     /// Set the current section/route.
     ///
     /// This triggers navigation-related musical events.
@@ -422,6 +494,52 @@ impl SonicEngine {
     pub fn set_modulation_rate(&mut self, rate: f32) {
         self.inner.set_modulation_rate(rate);
     }
+
+    /// Capture the engine's current musical state as a savable document.
+    #[wasm_bindgen]
+    pub fn snapshot(&self) -> Result<JsValue, JsError> {
+        Ok(serde_wasm_bindgen::to_value(&self.inner.snapshot())?)
+    }
+
+    /// Restore a previously captured snapshot, resuming exactly where it
+    /// left off.
+    #[wasm_bindgen]
+    pub fn restore(&mut self, snapshot: JsValue) -> Result<(), JsError> {
+        let snapshot: EngineSnapshot = serde_wasm_bindgen::from_value(snapshot)?;
+        self.inner.restore(&snapshot);
+        Ok(())
+    }
+
+    /// Register the engine's current state as a named preset in this
+    /// session's preset library.
+    #[wasm_bindgen]
+    pub fn save_preset(&mut self, name: &str) {
+        self.presets.register(name, self.inner.snapshot());
+    }
+
+    /// Restore a previously registered preset by name.
+    #[wasm_bindgen]
+    pub fn load_preset(&mut self, name: &str) -> Result<(), JsError> {
+        let snapshot = self
+            .presets
+            .get(name)
+            .ok_or_else(|| JsError::new(&format!("Unknown preset: {}", name)))?
+            .clone();
+        self.inner.restore(&snapshot);
+        Ok(())
+    }
+
+    /// Remove a named preset from this session's preset library.
+    #[wasm_bindgen]
+    pub fn remove_preset(&mut self, name: &str) -> bool {
+        self.presets.remove(name)
+    }
+
+    /// List the names of every preset registered in this session.
+    #[wasm_bindgen]
+    pub fn preset_names(&self) -> Vec<String> {
+        self.presets.names().iter().map(|n| n.to_string()).collect()
+    }
 }
 
 /// Convert note name to MIDI-style number (C=0, C#=1, etc.)